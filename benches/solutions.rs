@@ -0,0 +1,53 @@
+//! Benchmarks `part_one` and `part_two` of every day on the real input with criterion.
+//! Days are registered by `build.rs`, days without an input are skipped. Encrypted inputs are decrypted.
+use std::hint::black_box;
+
+use advent_of_code::template::encryption;
+use advent_of_code::template::input::get_path_for_puzzle;
+use advent_of_code::{configured_year, Day};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_day<A, B>(
+    c: &mut Criterion,
+    day: u8,
+    part_one: impl Fn(&str) -> A,
+    part_two: impl Fn(&str) -> B,
+) {
+    let Some(day) = Day::new(day) else {
+        return;
+    };
+
+    // inputs are read like the runner reads them, so encrypted inputs are benched too.
+    let path = get_path_for_puzzle(configured_year(), day);
+    if !encryption::exists(&path) {
+        eprintln!("Skipping day {day}: no input at \"{}\".", path.display());
+        return;
+    }
+
+    let input = match encryption::read_to_string(&path) {
+        Ok(input) if !input.is_empty() => input,
+        Ok(_) => {
+            eprintln!(
+                "Skipping day {day}: the input at \"{}\" is empty.",
+                path.display()
+            );
+            return;
+        }
+        Err(e) => {
+            eprintln!(
+                "Skipping day {day}: failed to read \"{}\": {e}",
+                path.display()
+            );
+            return;
+        }
+    };
+    let mut group = c.benchmark_group(day.to_string());
+    group.bench_function("part_one", |b| b.iter(|| part_one(black_box(&input))));
+    group.bench_function("part_two", |b| b.iter(|| part_two(black_box(&input))));
+    group.finish();
+}
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+criterion_group!(benches, bench_solutions);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+//! Registers every day in `src/bin` with the `solutions` benchmark, see `benches/solutions.rs`.
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days: Vec<(u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    let stem = path.file_stem()?.to_str()?;
+                    let day = stem.parse().ok().filter(|_| stem.len() == 2)?;
+                    Some((day, path.to_str()?.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    let mut out = String::new();
+
+    for (day, path) in &days {
+        out.push_str(&format!(
+            "#[allow(dead_code, unused_imports)]\n#[path = {path:?}]\nmod day_{day:02};\n\n"
+        ));
+    }
+
+    out.push_str("fn bench_solutions(c: &mut criterion::Criterion) {\n");
+    for (day, _) in &days {
+        out.push_str(&format!(
+            "    bench_day(c, {day}, day_{day:02}::part_one, day_{day:02}::part_two);\n"
+        ));
+    }
+    out.push_str("}\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(dest, out).unwrap();
+}
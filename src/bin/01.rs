@@ -6,7 +6,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         .map(|line| {
             let mut chars = line.chars().filter_map(|c| c.to_digit(10));
             let first = chars.next().expect("No numbers in line");
-            let second = chars.last().unwrap_or(first);
+            let second = chars.next_back().unwrap_or(first);
             format!("{}{}", first, second).parse::<u32>().unwrap()
         })
         .sum();
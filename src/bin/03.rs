@@ -78,7 +78,7 @@ fn parse_line_numbers(input: &str, row: usize) -> IResult<&str, Vec<(&str, usize
     Ok((remaining, numbers))
 }
 
-fn parse_numbers(input: &str) -> Vec<PartNumber> {
+fn parse_numbers(input: &str) -> Vec<PartNumber<'_>> {
     input
         .lines()
         .enumerate()
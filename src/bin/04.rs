@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse = parse_cards);
 
 #[derive(Debug)]
 struct Card {
@@ -7,7 +7,10 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, variants = {
+    part_one: [closed_form => part_one_closed_form],
+    part_two: [closed_form => part_two_closed_form],
+});
 
 #[derive(Debug)]
 struct Race {
@@ -16,20 +19,37 @@ struct Race {
 }
 
 impl Race {
-    fn record_beating_count(&self) -> u64 {
-        let mut range = 1..self.time;
+    fn beats_record(&self, held_time: u64) -> bool {
+        let remaining_time = self.time - held_time;
+        held_time * remaining_time > self.distance
+    }
 
-        let find_record = |held_time: &u64| {
-            let remaining_time = self.time - held_time;
-            let distance_traveled = held_time * remaining_time;
-            distance_traveled > self.distance
-        };
+    /// Scans from both ends for the first and last held time that beats the record.
+    fn record_beating_count(&self) -> u64 {
+        let find_record = |held_time: &u64| self.beats_record(*held_time);
 
-        let pad_start = range.find(find_record).unwrap();
-        let pad_end = range.rev().find(find_record).unwrap();
+        // separate ranges, a single winning held time is both the start and the end.
+        let pad_start = (1..self.time).find(find_record).unwrap();
+        let pad_end = (1..self.time).rev().find(find_record).unwrap();
 
         self.time - (pad_start - 1) - (self.time - pad_end)
     }
+
+    /// Solves `held * (time - held) > distance` with the quadratic formula.
+    /// The float estimate is nudged to the exact integer bound, and the end is symmetric to the start.
+    fn record_beating_count_closed_form(&self) -> u64 {
+        let discriminant = (self.time * self.time - 4 * self.distance) as f64;
+        let mut pad_start = ((self.time as f64 - discriminant.sqrt()) / 2.0).floor() as u64;
+
+        while !self.beats_record(pad_start) {
+            pad_start += 1;
+        }
+        while pad_start > 0 && self.beats_record(pad_start - 1) {
+            pad_start -= 1;
+        }
+
+        self.time - 2 * pad_start + 1
+    }
 }
 
 fn parse_segment(input: &str) -> IResult<&str, Vec<u64>> {
@@ -61,6 +81,17 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(record_beating_races.product())
 }
 
+pub fn part_one_closed_form(input: &str) -> Option<u64> {
+    let (_, races) = parse_races(input).unwrap();
+
+    Some(
+        races
+            .iter()
+            .map(Race::record_beating_count_closed_form)
+            .product(),
+    )
+}
+
 fn merge_values<T: FromStr<Err = ParseIntError> + ToString>(
     values: &[T],
 ) -> Result<T, ParseIntError> {
@@ -84,9 +115,51 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(race.record_beating_count())
 }
 
+pub fn part_two_closed_form(input: &str) -> Option<u64> {
+    let (_, race) = parse_race(input).unwrap();
+
+    Some(race.record_beating_count_closed_form())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::differential::{self, Differential, Rng};
+
+    struct ClosedFormVsScan;
+
+    impl Differential for ClosedFormVsScan {
+        type Input = (u64, u64);
+        type Output = u64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let time = rng.range(2..10_000);
+            let distance = rng.range(0..max_distance(time));
+            (time, distance)
+        }
+
+        fn is_valid(&(time, distance): &Self::Input) -> bool {
+            time >= 2 && distance < max_distance(time)
+        }
+
+        fn fast((time, distance): Self::Input) -> Self::Output {
+            Race { time, distance }.record_beating_count_closed_form()
+        }
+
+        fn reference((time, distance): Self::Input) -> Self::Output {
+            Race { time, distance }.record_beating_count()
+        }
+    }
+
+    /// The best distance for a race of `time`, which can't be beaten.
+    fn max_distance(time: u64) -> u64 {
+        (time / 2) * (time - time / 2)
+    }
+
+    #[test]
+    fn test_record_beating_count() {
+        differential::check::<ClosedFormVsScan>();
+    }
 
     #[test]
     fn test_part_one() {
@@ -109,7 +109,7 @@ pub fn part_two(input: &str) -> Option<u64> {
                 Instruction::Right => right,
             }
         }
-        println!("{steps}");
+        advent_of_code::trace!("cycle length: {steps}");
         steps as u64
     });
 
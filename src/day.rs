@@ -1,8 +1,56 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+use crate::template::aoc_cli;
+
+/// Puzzles unlock at midnight EST (UTC-5).
+const UNLOCK_UTC_OFFSET_SECS: i64 = -5 * 60 * 60;
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The most days an event can have, every day of December.
+pub const MAX_DAYS: u8 = 31;
+/// Overrides the number of days of the event, e.g. for puzzle events other than advent of code.
+pub const DAYS_ENV: &str = "AOC_DAYS";
+
+/// The year from which advent of code has 12 days instead of 25.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+static DAY_COUNT: OnceLock<u8> = OnceLock::new();
+static DAYS_FROM_ENV: OnceLock<Option<u8>> = OnceLock::new();
+
+/// Reads `AOC_DAYS`, if it is set to a valid number of days. An invalid value is only reported once.
+fn days_from_env() -> Option<u8> {
+    *DAYS_FROM_ENV.get_or_init(read_days_env)
+}
+
+fn read_days_env() -> Option<u8> {
+    let value = std::env::var(DAYS_ENV).ok()?;
+    match value.trim().parse() {
+        Ok(days) if (1..=MAX_DAYS).contains(&days) => Some(days),
+        _ => {
+            eprintln!(
+                "Ignoring {DAYS_ENV}=\"{value}\", expecting a number between 1 and {MAX_DAYS}."
+            );
+            None
+        }
+    }
+}
+
+/// The number of days of the event in `year`: 25 until 2024 and 12 since. `AOC_DAYS` overrides it.
+#[must_use]
+pub fn days_in_year(year: u16) -> u8 {
+    days_from_env().unwrap_or(if year < FIRST_SHORT_YEAR { 25 } else { 12 })
+}
+
+/// The number of days of the configured event, see [`configured_year`] and [`days_in_year`].
+pub fn day_count() -> u8 {
+    *DAY_COUNT.get_or_init(|| days_in_year(configured_year()))
+}
+
+/// A valid day number of the configured event (i.e. an integer in range 1 to [`day_count`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -19,7 +67,11 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in(day, day_count())
+    }
+
+    fn new_in(day: u8, days: u8) -> Option<Self> {
+        if day == 0 || day > days {
             return None;
         }
         Some(Self(day))
@@ -35,6 +87,87 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// Returns the day of advent it currently is in EST (UTC-5),
+    /// or [`None`] outside of the days of the event in December.
+    #[must_use]
+    pub fn today() -> Option<Self> {
+        Self::at(SystemTime::now())
+    }
+
+    /// Returns the day of advent it is in EST (UTC-5) at `time`,
+    /// or [`None`] outside of the days of that year's event in December.
+    #[must_use]
+    pub fn at(time: SystemTime) -> Option<Self> {
+        let (year, month, day) = est_date(time);
+        if month == 12 {
+            Self::new_in(day, days_in_year(year.try_into().ok()?))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the point in time the puzzle of this day unlocks in the event of `year`,
+    /// i.e. midnight EST (UTC-5) on this day of December.
+    #[must_use]
+    pub fn unlock_time(self, year: u16) -> SystemTime {
+        let days = days_from_civil(i64::from(year), 12, self.0);
+        let secs = days * SECS_PER_DAY - UNLOCK_UTC_OFFSET_SECS;
+        // NOTE: any year of advent of code is after the unix epoch.
+        UNIX_EPOCH + Duration::from_secs(secs.try_into().unwrap_or_default())
+    }
+}
+
+/// Returns the year it currently is in EST (UTC-5), i.e. the year of the latest event.
+#[must_use]
+pub fn current_year() -> u16 {
+    let (year, _, _) = est_date(SystemTime::now());
+    year.try_into().unwrap_or_default()
+}
+
+/// The year of the configured event, i.e. the one in `AOC_YEAR` or the current one.
+#[must_use]
+pub fn configured_year() -> u16 {
+    aoc_cli::get_year().unwrap_or_else(current_year)
+}
+
+/// Converts a point in time to a `(year, month, day)` date in EST (UTC-5).
+fn est_date(time: SystemTime) -> (i64, u8, u8) {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => i64::try_from(d.as_secs()).unwrap_or(i64::MAX),
+        Err(e) => -i64::try_from(e.duration().as_secs()).unwrap_or(i64::MAX),
+    };
+    civil_from_days((secs + UNLOCK_UTC_OFFSET_SECS).div_euclid(SECS_PER_DAY))
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` date.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u8, day as u8)
+}
+
+/// Converts a `(year, month, day)` date to days since the unix epoch.
+/// See: <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i64, month: u8, day: u8) -> i64 {
+    let (month, day) = (i64::from(month), i64::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
 }
 
 impl Display for Day {
@@ -74,26 +207,40 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", day_count())
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the configured event, see [`day_count`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event in `year`, see [`days_in_year`].
+#[must_use]
+pub fn all_days_in(year: u16) -> AllDays {
+    AllDays::until(days_in_year(year))
+}
+
+/// An iterator that yields every day of an event from the 1st to its last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(day_count())
+    }
+
+    fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(MAX_DAYS),
+        }
     }
 }
 
@@ -101,10 +248,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -114,16 +261,250 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
-/// Creates a [`Day`] value in a const context.
+/// A set of days of advent.
+///
+/// # Parsing
+/// A day set is a comma-separated list of days (`8`), inclusive ranges (`1-5` or `1..5`) and
+/// open ranges (`12..` or `..5`). Keywords such as `all` can be resolved with [`DaySet::parse_with`].
+///
+/// ```
+/// # use advent_of_code::DaySet;
+/// let days: DaySet = "1-3,8,10..12".parse().unwrap();
+/// assert_eq!(days.to_string(), "1-3,8,10-12");
+/// assert_eq!(days.len(), 7);
+/// ```
+///
+/// Open ranges end at the last day of the configured event, see [`day_count`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates an empty [`DaySet`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a [`DaySet`] that contains every day of the configured event.
+    #[must_use]
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    #[must_use]
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the day if this set contains exactly one day.
+    #[must_use]
+    pub fn single(&self) -> Option<Day> {
+        let mut iter = self.iter();
+        match (iter.next(), iter.next()) {
+            (Some(day), None) => Some(day),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns every day of advent that is not in this set.
+    #[must_use]
+    pub fn complement(&self) -> Self {
+        Self::all().difference(self)
+    }
+
+    /// Iterates the days of this set in ascending order.
+    #[must_use]
+    pub fn iter(&self) -> DaySetIter {
+        DaySetIter {
+            days: *self,
+            inner: all_days(),
+        }
+    }
+
+    /// Parses a day set, resolving words (e.g. `solved`) with `resolve_keyword`.
+    /// The keyword `all` is always understood.
+    pub fn parse_with(
+        s: &str,
+        resolve_keyword: impl Fn(&str) -> Option<DaySet>,
+    ) -> Result<Self, DaySetFromStrError> {
+        let mut days = Self::new();
+
+        for item in s.split(',').map(str::trim) {
+            let error = || DaySetFromStrError(item.to_string());
+
+            if item.is_empty() {
+                return Err(error());
+            }
+
+            if item.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                let resolved = match item {
+                    "all" => Some(Self::all()),
+                    keyword => resolve_keyword(keyword),
+                };
+                days = days.union(&resolved.ok_or_else(error)?);
+                continue;
+            }
+
+            let (start, end) = match item.split_once("..").or_else(|| item.split_once('-')) {
+                Some((start, end)) => (start.trim(), end.trim()),
+                None => (item, item),
+            };
+            let parse = |s: &str, default: u8| -> Result<Day, DaySetFromStrError> {
+                match s {
+                    "" => Ok(Day(default)),
+                    s => s.parse().map_err(|_| error()),
+                }
+            };
+            let (start, end) = (parse(start, 1)?, parse(end, day_count())?);
+
+            if start > end {
+                return Err(error());
+            }
+
+            (start.0..=end.0).for_each(|day| days.insert(Day(day)));
+        }
+
+        Ok(days)
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(u8, u8)> = vec![];
+
+        for day in self.iter() {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == day.0 => *end = day.0,
+                _ => ranges.push((day.0, day.0)),
+            }
+        }
+
+        let items: Vec<String> = ranges
+            .into_iter()
+            .map(|(start, end)| {
+                if start == end {
+                    start.to_string()
+                } else {
+                    format!("{start}-{end}")
+                }
+            })
+            .collect();
+
+        f.write_str(&items.join(","))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |_| None)
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        let mut days = Self::new();
+        days.insert(day);
+        days
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut days = Self::new();
+        iter.into_iter().for_each(|day| days.insert(day));
+        days
+    }
+}
+
+impl IntoIterator for DaySet {
+    type Item = Day;
+    type IntoIter = DaySetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the days of a [`DaySet`] in ascending order.
+pub struct DaySetIter {
+    days: DaySet,
+    inner: AllDays,
+}
+
+impl Iterator for DaySetIter {
+    type Item = Day;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let days = self.days;
+        self.inner.find(|day| days.contains(*day))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days between 1 and {} like `1-5,8,10..`",
+            self.0,
+            day_count()
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Day`] value in a const context. The number of days of the configured event is only known at runtime,
+/// so this accepts every day of December.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and 31"
             ),
         );
         $crate::Day::__new_unchecked($day)
@@ -134,11 +515,16 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{civil_from_days, day_count, days_from_civil, days_in_year, AllDays, Day, DaySet};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn days(days: &[u8]) -> DaySet {
+        days.iter().map(|day| Day(*day)).collect()
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::until(25);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -166,6 +552,91 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(24)));
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
+
+        assert_eq!(AllDays::until(12).last(), Some(Day(12)));
+    }
+
+    #[test]
+    fn counts_days_of_events() {
+        assert_eq!(days_in_year(2015), 25);
+        assert_eq!(days_in_year(2024), 25);
+        assert_eq!(days_in_year(2025), 12);
+    }
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_692), (2023, 12, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+        assert_eq!(days_from_civil(2024, 3, 1), 19_783);
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        // 2023-12-01T05:00:00Z
+        let unlock = UNIX_EPOCH + Duration::from_secs(1_701_406_800);
+        assert_eq!(Day(1).unlock_time(2023), unlock);
+        assert_eq!(Day::at(unlock), Some(Day(1)));
+        assert_eq!(Day::at(unlock - Duration::from_secs(1)), None);
+        assert_eq!(
+            Day::at(Day(25).unlock_time(2023) + Duration::from_secs(86_399)),
+            Some(Day(25))
+        );
+        assert_eq!(
+            Day::at(Day(25).unlock_time(2023) + Duration::from_secs(86_400)),
+            None
+        );
+        assert_eq!(Day::at(Day(12).unlock_time(2025)), Some(Day(12)));
+        assert_eq!(Day::at(Day(13).unlock_time(2025)), None);
+    }
+
+    #[test]
+    fn day_set_parses_items() {
+        assert_eq!("8".parse::<DaySet>().unwrap(), days(&[8]));
+        assert_eq!("1-3,8".parse::<DaySet>().unwrap(), days(&[1, 2, 3, 8]));
+        assert_eq!("2..4".parse::<DaySet>().unwrap(), days(&[2, 3, 4]));
+        assert_eq!(
+            "10..".parse::<DaySet>().unwrap(),
+            (10..=day_count()).map(Day).collect()
+        );
+        assert_eq!("..2, 5".parse::<DaySet>().unwrap(), days(&[1, 2, 5]));
+        assert_eq!("all".parse::<DaySet>().unwrap(), DaySet::all());
+    }
+
+    #[test]
+    fn day_set_rejects_invalid_items() {
+        assert!("0".parse::<DaySet>().is_err());
+        assert!((day_count() + 1).to_string().parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("solved".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn day_set_resolves_keywords() {
+        let resolve = |keyword: &str| (keyword == "odd").then(|| days(&[1, 3, 5]));
+        let parsed = DaySet::parse_with("odd,2", resolve).unwrap();
+        assert_eq!(parsed, days(&[1, 2, 3, 5]));
+    }
+
+    #[test]
+    fn day_set_operations() {
+        let a = days(&[1, 2, 3]);
+        let b = days(&[3, 4]);
+        assert_eq!(a.union(&b), days(&[1, 2, 3, 4]));
+        assert_eq!(a.intersection(&b), days(&[3]));
+        assert_eq!(a.difference(&b), days(&[1, 2]));
+        assert_eq!(a.complement().len(), usize::from(day_count()) - 3);
+        assert_eq!(b.iter().collect::<Vec<_>>(), vec![Day(3), Day(4)]);
+        assert_eq!(days(&[7]).single(), Some(Day(7)));
+        assert_eq!(a.single(), None);
+    }
+
+    #[test]
+    fn day_set_displays_ranges() {
+        assert_eq!(days(&[1, 2, 3, 5, 7, 8]).to_string(), "1-3,5,7-8");
+        assert_eq!(DaySet::new().to_string(), "");
     }
 }
 
@@ -1,72 +1,267 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, auth, completions, crosscheck, decrypt, download, encrypt, leaderboard, profile, read,
+    scaffold, solve, status, view,
+};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
+    use std::time::Duration;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::{
+        aoc_cli::parse_year,
+        budget::Budget,
+        cli::{self, parse_part, Matches, Shell},
+        commands::{auth, leaderboard, solve::Variant},
+        export::{parse_export, Export},
+        input::Input,
+        readme_benchmarks::{self, parse_columns},
+        selection::{parse_day, parse_day_set},
+    };
+    use advent_of_code::{Day, DaySet};
 
     pub enum AppArguments {
+        Auth {
+            action: auth::Action,
+            profile: Option<String>,
+        },
+        Completions {
+            shell: Shell,
+        },
+        Crosscheck {
+            days: DaySet,
+            input_set: Option<String>,
+            release: bool,
+        },
+        Decrypt {
+            days: DaySet,
+        },
         Download {
+            /// `None` for `--all`.
+            days: Option<DaySet>,
+            year: Option<u16>,
+            wait: bool,
+            refresh: bool,
+        },
+        Encrypt {
+            days: DaySet,
+        },
+        Leaderboard {
+            source: leaderboard::Source,
+            year: Option<u16>,
+            day: Option<Day>,
+        },
+        Profile {
             day: Day,
+            part: Option<u8>,
+            frequency: u32,
+            duration: Duration,
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
-            day: Day,
+            days: DaySet,
         },
         Solve {
-            day: Day,
+            days: DaySet,
             release: bool,
             time: bool,
+            trace: bool,
             submit: Option<u8>,
+            budget: Budget,
+            heap: bool,
+            variant: Variant,
+            input: Input,
         },
         All {
             release: bool,
             time: bool,
+            budget: Budget,
+            jobs: usize,
+            readme: readme_benchmarks::Config,
+            export: Option<Export>,
+            heap: bool,
+            days: DaySet,
+        },
+        Status {
+            with_tests: bool,
+            days: DaySet,
+        },
+        View {
+            day: Day,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut args = std::env::args().skip(1);
+
+        let name = match args.next() {
+            None => {
+                eprint!("{}", cli::app_help());
+                process::exit(1);
+            }
+            Some(name) if matches!(name.as_str(), "help" | "--help" | "-h") => {
+                print!("{}", cli::app_help());
+                process::exit(0);
+            }
+            Some(name) => name,
+        };
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let command = cli::find_command(&name).ok_or(cli::Error::UnknownCommand(name))?;
+        let args = cli::parse(command, args)?;
+
+        if args.help {
+            print!("{}", cli::command_help(command));
+            process::exit(0);
+        }
+
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                budget: parse_budget(&args)?,
+                jobs: args.value_from_str("--jobs")?.unwrap_or(1),
+                readme: parse_readme_config(&args)?,
+                export: args.value_from_fn("--export", parse_export)?,
+                heap: args.contains("--heap"),
+                days: args
+                    .free_from_fn(parse_day_set)?
+                    .unwrap_or_else(DaySet::all),
+            },
+            "auth" => AppArguments::Auth {
+                action: args.required_free_from_fn(str::parse)?,
+                profile: args.value("--profile").map(String::from),
             },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "completions" => AppArguments::Completions {
+                shell: args.required_free_from_fn(str::parse)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "crosscheck" => AppArguments::Crosscheck {
+                days: args
+                    .free_from_fn(parse_day_set)?
+                    .unwrap_or_else(DaySet::all),
+                input_set: args.value("--input-set").map(String::from),
+                release: args.contains("--release"),
+            },
+            "decrypt" => AppArguments::Decrypt {
+                days: args
+                    .free_from_fn(parse_day_set)?
+                    .unwrap_or_else(DaySet::all),
+            },
+            "download" => AppArguments::Download {
+                days: parse_download_days(&args)?,
+                year: args.value_from_fn("--year", parse_year)?,
+                wait: args.contains("--wait"),
+                refresh: args.contains("--refresh"),
+            },
+            "encrypt" => AppArguments::Encrypt {
+                days: args
+                    .free_from_fn(parse_day_set)?
+                    .unwrap_or_else(DaySet::all),
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "leaderboard" => AppArguments::Leaderboard {
+                source: parse_leaderboard_source(&args)?,
+                year: args.value_from_fn("--year", parse_year)?,
+                day: args.value_from_fn("--day", parse_day)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            "profile" => AppArguments::Profile {
+                part: args.value_from_fn("--part", parse_part)?,
+                frequency: args.value_from_str("--frequency")?.unwrap_or(999),
+                duration: args
+                    .value_from_fn("--duration", cli::parse_secs)?
+                    .unwrap_or(Duration::from_secs(5)),
+                day: args.required_free_from_fn(parse_day)?,
+            },
+            "read" => AppArguments::Read {
+                day: args.required_free_from_fn(parse_day)?,
+                part: args.value_from_fn("--part", parse_part)?,
+            },
+            "scaffold" => AppArguments::Scaffold {
+                days: args.required_free_from_fn(parse_day_set)?,
+            },
+            "solve" => AppArguments::Solve {
+                days: args.required_free_from_fn(parse_day_set)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
+                submit: args.value_from_fn("--submit", parse_part)?,
                 time: args.contains("--time"),
+                trace: parse_trace(&args)?,
+                budget: parse_budget(&args)?,
+                heap: args.contains("--heap"),
+                variant: parse_variant(&args),
+                input: Input::from_matches(&args)?,
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            "status" => AppArguments::Status {
+                with_tests: !args.contains("--no-tests"),
+                days: args
+                    .free_from_fn(parse_day_set)?
+                    .unwrap_or_else(DaySet::all),
+            },
+            "view" => AppArguments::View {
+                day: args.required_free_from_fn(parse_day)?,
+            },
+            name => unreachable!("command \"{name}\" is defined but not handled"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        Ok(app_args)
+    }
+
+    fn parse_readme_config(args: &Matches) -> Result<readme_benchmarks::Config, cli::Error> {
+        Ok(readme_benchmarks::Config {
+            columns: args
+                .value_from_fn("--columns", parse_columns)?
+                .unwrap_or_default(),
+            format: args.value_from_str("--format")?.unwrap_or_default(),
+            metadata: args.contains("--metadata"),
+            svg: args.value_from_str("--svg")?,
+        })
+    }
+
+    fn parse_download_days(args: &Matches) -> Result<Option<DaySet>, cli::Error> {
+        let days = args.free_from_fn(parse_day_set)?;
+        match (days, args.contains("--all")) {
+            (Some(_), true) => Err(cli::Error::Conflict("--all", "<days>")),
+            (None, true) if args.contains("--wait") => Err(cli::Error::Conflict("--all", "--wait")),
+            (None, false) => Err(cli::Error::MissingArgument("days")),
+            (days, _) => Ok(days),
         }
+    }
 
-        Ok(app_args)
+    fn parse_leaderboard_source(args: &Matches) -> Result<leaderboard::Source, cli::Error> {
+        let id = args.free_from_fn(str::parse)?;
+        match (id, args.value("--file")) {
+            (Some(_), Some(_)) => Err(cli::Error::Conflict("--file", "<id>")),
+            (None, Some(_)) if args.contains("--year") => {
+                Err(cli::Error::Conflict("--file", "--year"))
+            }
+            (Some(id), None) => Ok(leaderboard::Source::Fetch(id)),
+            (None, Some(path)) => Ok(leaderboard::Source::File(path.into())),
+            (None, None) => Err(cli::Error::MissingArgument("id")),
+        }
+    }
+
+    fn parse_variant(args: &Matches) -> Variant {
+        if args.contains("--all-variants") {
+            return Variant::All;
+        }
+
+        args.value("--variant")
+            .map_or(Variant::Default, |name| Variant::Named(name.into()))
+    }
+
+    /// Tracing is compiled out of optimized builds, so `--trace` would print nothing with `--release`.
+    fn parse_trace(args: &Matches) -> Result<bool, cli::Error> {
+        match (args.contains("--trace"), args.contains("--release")) {
+            (true, true) => Err(cli::Error::Conflict("--trace", "--release")),
+            (trace, _) => Ok(trace),
+        }
+    }
+
+    fn parse_budget(args: &Matches) -> Result<Budget, cli::Error> {
+        Ok(Budget::new(
+            args.value_from_fn("--timeout", cli::parse_secs)?,
+            args.value_from_str("--memory")?,
+        ))
     }
 }
 
@@ -77,16 +272,72 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+            AppArguments::All {
+                release,
+                time,
+                budget,
+                jobs,
+                readme,
+                export,
+                heap,
+                days,
+            } => all::handle(
+                days,
+                release,
+                time,
+                budget,
+                jobs,
+                &readme,
+                export.as_ref(),
+                heap,
+            ),
+            AppArguments::Auth { action, profile } => auth::handle(&action, profile.as_deref()),
+            AppArguments::Completions { shell } => completions::handle(shell),
+            AppArguments::Crosscheck {
+                days,
+                input_set,
+                release,
+            } => crosscheck::handle(days, input_set.as_deref(), release),
+            AppArguments::Decrypt { days } => decrypt::handle(&days),
+            AppArguments::Download {
+                days: Some(days),
+                year,
+                wait,
+                refresh,
+            } => download::handle(days, year, wait, refresh),
+            AppArguments::Download {
+                days: None,
+                year,
+                refresh,
+                ..
+            } => download::handle_all(year, refresh),
+            AppArguments::Encrypt { days } => encrypt::handle(&days),
+            AppArguments::Leaderboard { source, year, day } => {
+                leaderboard::handle(&source, year, day);
+            }
+            AppArguments::Profile {
                 day,
+                part,
+                frequency,
+                duration,
+            } => profile::handle(day, part, frequency, duration),
+            AppArguments::Read { day, part } => read::handle(day, part),
+            AppArguments::Scaffold { days } => scaffold::handle(days),
+            AppArguments::Solve {
+                days,
                 release,
                 time,
+                trace,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                budget,
+                heap,
+                variant,
+                input,
+            } => solve::handle(
+                days, release, time, trace, submit, budget, heap, &variant, &input,
+            ),
+            AppArguments::Status { with_tests, days } => status::handle(days, with_tests),
+            AppArguments::View { day } => view::handle(day),
         },
     };
 }
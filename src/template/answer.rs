@@ -0,0 +1,225 @@
+/// Answers that are drawn instead of computed, e.g. letters made of `#` characters.
+/// Provides an OCR for the block-letter font used by advent of code and helpers to render grids.
+use std::fmt::Display;
+
+/// Character used for lit cells when rendering a grid.
+pub const LIT: char = '█';
+/// Character used for dark cells when rendering a grid.
+pub const DARK: char = ' ';
+
+/// The 4x6 block-letter font, one letter per run of non-empty columns.
+const FONT_LETTERS: &str = "ABCEFGHIJKLOPRSUYZ";
+const FONT: [&str; 6] = [
+    ".##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#",
+    "#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.",
+    "####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..",
+    "#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...",
+    "#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####",
+];
+
+/// A puzzle answer that may come with a picture.
+///
+/// # Display
+/// Displays the text, which is what gets submitted. The alternate form (`{:#}`), which the runner uses,
+/// displays the picture (if any) above the text, which makes the runner print it on its own lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    text: String,
+    picture: Option<String>,
+}
+
+impl Answer {
+    /// Creates an [`Answer`] without a picture.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            picture: None,
+        }
+    }
+
+    /// Reads the letters drawn in `picture`, returns [`None`] if a glyph is not recognized.
+    /// Lit cells are `#` or `█`, everything else is considered dark.
+    #[must_use]
+    pub fn from_picture(picture: &str) -> Option<Self> {
+        let picture = picture.trim_matches('\n');
+        Some(Self {
+            text: ocr(picture)?,
+            picture: Some(picture.to_string()),
+        })
+    }
+
+    /// Reads the letters drawn by the lit cells of `grid`, see [`Answer::from_picture`].
+    #[must_use]
+    pub fn from_grid(grid: &[Vec<bool>]) -> Option<Self> {
+        Self::from_picture(&render_grid(grid))
+    }
+
+    /// Reads the letters drawn by the lit `(x, y)` coordinates, see [`Answer::from_picture`].
+    #[must_use]
+    pub fn from_points(points: impl IntoIterator<Item = (usize, usize)>) -> Option<Self> {
+        Self::from_picture(&render_points(points))
+    }
+
+    /// The value that is submitted for this answer.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    #[must_use]
+    pub fn picture(&self) -> Option<&str> {
+        self.picture.as_deref()
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.picture {
+            Some(picture) if f.alternate() => write!(f, "{picture}\n{}", self.text),
+            _ => f.write_str(&self.text),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders a grid of lit cells as text, one line per row.
+#[must_use]
+pub fn render_grid(grid: &[Vec<bool>]) -> String {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|lit| if *lit { LIT } else { DARK })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders lit `(x, y)` coordinates as text, cropped to their bounding box.
+#[must_use]
+pub fn render_points(points: impl IntoIterator<Item = (usize, usize)>) -> String {
+    let points: Vec<_> = points.into_iter().collect();
+
+    let (Some(min_x), Some(min_y)) = (
+        points.iter().map(|p| p.0).min(),
+        points.iter().map(|p| p.1).min(),
+    ) else {
+        return String::new();
+    };
+    let max_x = points.iter().map(|p| p.0).max().unwrap_or(min_x);
+    let max_y = points.iter().map(|p| p.1).max().unwrap_or(min_y);
+
+    let mut grid = vec![vec![false; max_x - min_x + 1]; max_y - min_y + 1];
+    for (x, y) in points {
+        grid[y - min_y][x - min_x] = true;
+    }
+
+    render_grid(&grid)
+}
+
+/// Reads the letters drawn in `picture` using the 4x6 advent of code font.
+/// Returns [`None`] if the picture is empty or contains an unknown glyph.
+#[must_use]
+pub fn ocr(picture: &str) -> Option<String> {
+    let font: Vec<_> = glyphs(&FONT.map(|row| row.chars().map(is_lit).collect()));
+    let rows: Vec<Vec<bool>> = picture
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    let letters: Vec<_> = glyphs(&rows);
+    if letters.is_empty() {
+        return None;
+    }
+
+    letters
+        .iter()
+        .map(|glyph| {
+            let index = font.iter().position(|known| known == glyph)?;
+            FONT_LETTERS.chars().nth(index)
+        })
+        .collect()
+}
+
+fn is_lit(c: char) -> bool {
+    c == '#' || c == LIT
+}
+
+/// Splits rows of cells into glyphs at columns that are dark in every row.
+fn glyphs(rows: &[Vec<bool>]) -> Vec<Vec<Vec<bool>>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let cell = |row: &Vec<bool>, x: usize| row.get(x).copied().unwrap_or(false);
+    let is_empty_column = |x: usize| rows.iter().all(|row| !cell(row, x));
+
+    let mut glyphs = vec![];
+    let mut x = 0;
+
+    while x < width {
+        if is_empty_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_empty_column(x) {
+            x += 1;
+        }
+
+        glyphs.push(
+            rows.iter()
+                .map(|row| (start..x).map(|x| cell(row, x)).collect())
+                .collect(),
+        );
+    }
+
+    glyphs
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ocr, render_points, Answer};
+
+    const HELLO: &str = "\
+#..#.####.#....#.....##.
+#..#.#....#....#....#..#
+####.###..#....#....#..#
+#..#.#....#....#....#..#
+#..#.#....#....#....#..#
+#..#.####.####.####..##.";
+
+    #[test]
+    fn reads_letters() {
+        assert_eq!(ocr(HELLO), Some("HELLO".into()));
+    }
+
+    #[test]
+    fn reads_every_font_letter() {
+        assert_eq!(
+            ocr(&super::FONT.join("\n")),
+            Some(super::FONT_LETTERS.into())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(ocr("#.#\n.#.\n#.#"), None);
+        assert_eq!(ocr(""), None);
+    }
+
+    #[test]
+    fn renders_points_cropped() {
+        let rendered = render_points([(2, 1), (3, 2)]);
+        assert_eq!(rendered, "█ \n █");
+    }
+
+    #[test]
+    fn answer_submits_text_and_displays_picture() {
+        let answer = Answer::from_picture(HELLO).unwrap();
+        assert_eq!(answer.to_string(), "HELLO");
+        assert_eq!(format!("{answer:#}"), format!("{HELLO}\nHELLO"));
+    }
+}
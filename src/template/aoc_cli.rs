@@ -4,13 +4,18 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::Day;
+use crate::template::{http, session};
+use crate::{current_year, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// aoc-cli failed and no session cookie is set.
+    MissingSession,
+    /// aoc-cli failed and adventofcode.com rejects the session cookie.
+    InvalidSession,
     IoError,
 }
 
@@ -22,6 +27,8 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::MissingSession => write!(f, "{}", http::Error::MissingSession),
+            AocCommandError::InvalidSession => write!(f, "{}", http::Error::InvalidSession),
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
         }
     }
@@ -35,62 +42,49 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
-
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_captured(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Whether the output of [`submit`] says that the answer was right.
+#[must_use]
+pub fn is_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
+}
+
+/// The year of the first event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// An error which is returned for a year without an event.
+#[derive(Debug)]
+pub struct InvalidYearError(String);
+
+impl std::error::Error for InvalidYearError {}
+
+impl Display for InvalidYearError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "there is no event in \"{}\", events run every year since {FIRST_YEAR}",
+            self.0
+        )
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+/// Parses the year of an event, e.g. for `download --year <year>`.
+pub fn parse_year(s: &str) -> Result<u16, InvalidYearError> {
+    s.parse()
+        .ok()
+        .filter(|year| (FIRST_YEAR..=current_year()).contains(year))
+        .ok_or_else(|| InvalidYearError(s.into()))
 }
 
-fn get_year() -> Option<u16> {
+/// Returns the year of the event set in `AOC_YEAR`, if any.
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -100,6 +94,15 @@ fn get_year() -> Option<u16> {
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
+    // aoc-cli reads the default profile's session file by itself.
+    let profile = session::active_profile();
+    if profile != session::DEFAULT_PROFILE && !session::is_overridden(&profile) {
+        if let Some(path) = session::get_path_for_session(&profile) {
+            cmd_args.push("--session-file".into());
+            cmd_args.push(path.display().to_string());
+        }
+    }
+
     if let Some(year) = get_year() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
@@ -110,18 +113,32 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
+/// aoc-cli fails the same way for any problem, check whether the session cookie is the cause.
+fn diagnose(output: Output) -> AocCommandError {
+    let Some(token) = session::load(&session::active_profile()) else {
+        return AocCommandError::MissingSession;
+    };
+
+    match session::validate(&token) {
+        Ok(false) => AocCommandError::InvalidSession,
+        Ok(true) | Err(_) => AocCommandError::BadExitStatus(output),
+    }
+}
+
+/// Calls aoc-cli and captures stdout (and prints it afterwards) so it can be inspected.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(Stdio::piped())
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
     if output.status.success() {
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(diagnose(output))
     }
 }
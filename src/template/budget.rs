@@ -0,0 +1,215 @@
+/// Per-part time and memory budgets.
+///
+/// When a budget is set, every part runs in its own child process (the day binary re-invoked with
+/// `--part <n>`), which is killed once it exceeds the budget. The remaining parts keep running.
+use std::fmt::Display;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{env, fs, thread};
+
+use super::input::{read_stdin, Input};
+use super::runner::Options;
+use super::{ANSI_BOLD, ANSI_RESET};
+
+pub const TIMED_OUT: &str = "timed out";
+pub const OUT_OF_MEMORY: &str = "out of memory";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Limits for a single part. Both are measured for the whole run of a part, including benchmarks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Budget {
+    pub timeout: Option<Duration>,
+    /// Peak resident memory in MiB. Only enforced on Linux.
+    pub memory: Option<u64>,
+}
+
+impl Budget {
+    /// Creates a [`Budget`] from a timeout and a memory limit in MiB.
+    #[must_use]
+    pub fn new(timeout: Option<Duration>, memory_mib: Option<u64>) -> Self {
+        Self {
+            timeout,
+            memory: memory_mib,
+        }
+    }
+
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+
+    /// The arguments that pass this budget on to a day binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+        if let Some(memory) = self.memory {
+            args.push("--memory".into());
+            args.push(memory.to_string());
+        }
+        args
+    }
+}
+
+/// How a supervised part ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Finished,
+    TimedOut,
+    OutOfMemory,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Finished => f.write_str("finished"),
+            Outcome::TimedOut => f.write_str(TIMED_OUT),
+            Outcome::OutOfMemory => f.write_str(OUT_OF_MEMORY),
+        }
+    }
+}
+
+/// What the runner should do with a part.
+pub enum Supervision {
+    /// Run the part in this process.
+    Run,
+    /// This process is a child for another part.
+    Skip,
+    /// The part ran in a child process.
+    Supervised(Outcome),
+}
+
+/// Decides whether a part runs in this process, and runs it in a budgeted child process if needed.
+pub fn supervise(part: u8, options: &Options) -> Supervision {
+    if let Some(child_part) = options.part {
+        return if child_part == part {
+            Supervision::Run
+        } else {
+            Supervision::Skip
+        };
+    }
+
+    let budget = options.budget;
+    if budget.is_unlimited() {
+        return Supervision::Run;
+    }
+
+    let outcome = match spawn_part(options, part) {
+        Ok(child) => watch(child, budget),
+        Err(e) => {
+            eprintln!("Failed to run part {part} in a child process: {e}");
+            Outcome::Finished
+        }
+    };
+
+    let part_str = format!("Part {part}");
+    match outcome {
+        Outcome::Finished => {}
+        Outcome::TimedOut => println!(
+            "\r{part_str}: {ANSI_BOLD}{TIMED_OUT}{ANSI_RESET} (> {:.1?})",
+            budget.timeout.unwrap_or_default()
+        ),
+        Outcome::OutOfMemory => println!(
+            "\r{part_str}: {ANSI_BOLD}{OUT_OF_MEMORY}{ANSI_RESET} (> {} MiB)",
+            budget.memory.unwrap_or_default()
+        ),
+    }
+
+    Supervision::Supervised(outcome)
+}
+
+fn spawn_part(options: &Options, part: u8) -> std::io::Result<Child> {
+    let child = Options {
+        part: Some(part),
+        budget: Budget::default(),
+        ..options.clone()
+    };
+
+    let mut command = Command::new(env::current_exe()?);
+    command.args(child.to_args());
+
+    if options.input != Input::Stdin {
+        return command.spawn();
+    }
+
+    // the parent already consumed stdin, pass it on.
+    let mut child = command.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(read_stdin()?.as_bytes())?;
+    }
+    Ok(child)
+}
+
+fn watch(mut child: Child, budget: Budget) -> Outcome {
+    let timer = Instant::now();
+
+    if budget.memory.is_some() && cfg!(not(target_os = "linux")) {
+        eprintln!("Warning: memory budgets are only enforced on Linux.");
+    }
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => return Outcome::Finished,
+            Ok(None) => {}
+        }
+
+        let exceeded = if budget.timeout.is_some_and(|t| timer.elapsed() > t) {
+            Some(Outcome::TimedOut)
+        } else if budget
+            .memory
+            .zip(peak_memory_mib(child.id()))
+            .is_some_and(|(limit, peak)| peak > limit)
+        {
+            Some(Outcome::OutOfMemory)
+        } else {
+            None
+        };
+
+        if let Some(outcome) = exceeded {
+            let _ = child.kill();
+            let _ = child.wait();
+            return outcome;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Reads the peak resident set size of a process from procfs.
+fn peak_memory_mib(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib / 1024)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Budget;
+
+    #[test]
+    fn creates_budget() {
+        let budget = Budget::new(Some(Duration::from_millis(2500)), Some(512));
+        assert_eq!(budget.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(budget.memory, Some(512));
+        assert!(Budget::new(None, None).is_unlimited());
+    }
+
+    #[test]
+    fn converts_to_args() {
+        assert_eq!(
+            Budget::new(Some(Duration::from_secs(10)), Some(64)).to_args(),
+            ["--timeout", "10", "--memory", "64"]
+        );
+        assert!(Budget::default().to_args().is_empty());
+    }
+}
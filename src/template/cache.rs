@@ -0,0 +1,166 @@
+/// A local cache of pages fetched from adventofcode.com, in `data/cache`.
+///
+/// Accounts see different pages, so every session profile has its own directory. A page is stored as
+/// `data/cache/{profile}/{key}`, e.g. `2023-day-1` for `/2023/day/1`, next to `{key}.meta` which records its path and
+/// when it was fetched. Inputs are not cached, they are only written to `data/{year}/inputs`, where they can be
+/// encrypted.
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs, io};
+
+use crate::template::http::{self, Endpoint};
+use crate::template::session;
+
+const META_EXTENSION: &str = "meta";
+
+#[derive(Debug)]
+pub struct Entry {
+    pub body: String,
+    pub fetched_at: SystemTime,
+    /// Whether the entry was read from the cache rather than fetched.
+    pub cached: bool,
+}
+
+impl Entry {
+    /// The time since the page was fetched, e.g. `3h`.
+    #[must_use]
+    pub fn age(&self) -> String {
+        format_age(
+            SystemTime::now()
+                .duration_since(self.fetched_at)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[must_use]
+pub fn get_dir() -> PathBuf {
+    env::current_dir().unwrap().join("data").join("cache")
+}
+
+fn get_dir_for_profile(profile: &str) -> PathBuf {
+    get_dir().join(profile)
+}
+
+fn get_key(path: &str) -> String {
+    path.trim_matches('/').replace('/', "-")
+}
+
+/// The paths of the page and its meta file in the cache of the active session profile.
+fn get_paths(path: &str) -> (PathBuf, PathBuf) {
+    let dir = get_dir_for_profile(&session::active_profile());
+    let key = get_key(path);
+    (dir.join(&key), dir.join(format!("{key}.{META_EXTENSION}")))
+}
+
+fn format_meta(path: &str, fetched_at: SystemTime) -> String {
+    let secs = fetched_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!("path = {path}\nfetched_at = {secs}\n")
+}
+
+fn parse_meta(contents: &str) -> Option<SystemTime> {
+    contents.lines().find_map(|line| {
+        let secs = line.strip_prefix("fetched_at = ")?.parse().ok()?;
+        Some(UNIX_EPOCH + Duration::from_secs(secs))
+    })
+}
+
+#[must_use]
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => format!("{secs}s"),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+/// The cached page for a path, if it was fetched before with the active session profile.
+#[must_use]
+pub fn load(path: &str) -> Option<Entry> {
+    let (page, meta) = get_paths(path);
+    let body = fs::read_to_string(page).ok()?;
+    let fetched_at = parse_meta(&fs::read_to_string(meta).ok()?)?;
+
+    Some(Entry {
+        body,
+        fetched_at,
+        cached: true,
+    })
+}
+
+pub fn store(path: &str, body: &str, fetched_at: SystemTime) -> io::Result<()> {
+    let (page, meta) = get_paths(path);
+    fs::create_dir_all(page.parent().unwrap())?;
+    fs::write(page, body)?;
+    fs::write(meta, format_meta(path, fetched_at))
+}
+
+/// Returns the cached page for a path, or fetches and caches it. `refresh` skips the cache.
+/// Inputs are fetched with [`http::get`] instead, so they never end up in the cache.
+pub fn fetch(endpoint: Endpoint, path: &str, refresh: bool) -> Result<Entry, http::Error> {
+    if !refresh {
+        if let Some(entry) = load(path) {
+            return Ok(entry);
+        }
+    }
+
+    let body = http::get(endpoint, path)?;
+    let fetched_at = SystemTime::now();
+
+    if let Err(e) = store(path, &body, fetched_at) {
+        eprintln!("Failed to cache {path}: {e}");
+    }
+
+    Ok(Entry {
+        body,
+        fetched_at,
+        cached: false,
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{format_age, format_meta, get_dir, get_dir_for_profile, get_key, parse_meta};
+
+    #[test]
+    fn derives_keys_from_paths() {
+        assert_eq!(get_key("/2023/day/1"), "2023-day-1");
+        assert_eq!(
+            get_key("/2023/leaderboard/private/view/1.json"),
+            "2023-leaderboard-private-view-1.json"
+        );
+    }
+
+    #[test]
+    fn separates_profiles() {
+        assert_eq!(get_dir_for_profile("default"), get_dir().join("default"));
+        assert_ne!(get_dir_for_profile("default"), get_dir_for_profile("work"));
+    }
+
+    #[test]
+    fn round_trips_meta() {
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(1_701_406_800);
+        let meta = format_meta("/2023/day/1", fetched_at);
+
+        assert_eq!(meta, "path = /2023/day/1\nfetched_at = 1701406800\n");
+        assert_eq!(parse_meta(&meta), Some(fetched_at));
+        assert_eq!(parse_meta("path = /2023/day/1\n"), None);
+    }
+
+    #[test]
+    fn formats_age() {
+        assert_eq!(format_age(Duration::from_secs(42)), "42s");
+        assert_eq!(format_age(Duration::from_secs(150)), "2m");
+        assert_eq!(format_age(Duration::from_secs(7300)), "2h");
+        assert_eq!(format_age(Duration::from_secs(200_000)), "2d");
+    }
+}
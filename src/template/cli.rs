@@ -0,0 +1,977 @@
+/// Declarative definitions of the command line, for both the `cargo <command>` aliases and the day binaries.
+///
+/// Arguments are parsed against these definitions, which also generate `--help` and the shell completion scripts, so
+/// that a flag only has to be declared once.
+use std::collections::BTreeMap;
+use std::error;
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A flag of a command. Flags with a `value` take an argument, e.g. `--jobs <n>`.
+pub struct Flag {
+    pub name: &'static str,
+    pub value: Option<&'static str>,
+    /// Whether the value may be left out, e.g. `--example [n]`. It is only taken from the next argument if that
+    /// does not start with `-`.
+    pub optional: bool,
+    pub help: &'static str,
+    /// Values suggested by shell completions.
+    pub choices: &'static [&'static str],
+}
+
+/// The free argument of a command, e.g. the day in `cargo solve 01`.
+pub struct Positional {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub required: bool,
+    /// Values suggested by shell completions.
+    pub choices: &'static [&'static str],
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positional: Option<Positional>,
+    pub flags: &'static [Flag],
+}
+
+const fn switch(name: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: None,
+        optional: false,
+        help,
+        choices: &[],
+    }
+}
+
+const fn option(name: &'static str, value: &'static str, help: &'static str) -> Flag {
+    Flag {
+        name,
+        value: Some(value),
+        optional: false,
+        help,
+        choices: &[],
+    }
+}
+
+const fn days(required: bool) -> Positional {
+    Positional {
+        name: "days",
+        help: "Days to select, e.g. 1, 1-5,8, 12.., solved, unsolved, scaffolded or today",
+        required,
+        choices: &["solved", "unsolved", "scaffolded", "today"],
+    }
+}
+
+const fn day() -> Positional {
+    Positional {
+        name: "day",
+        help: "A day number or today",
+        required: true,
+        choices: &["today"],
+    }
+}
+
+/// The placeholder of flags that take a file path, which completions suggest files for.
+const PATH: &str = "path";
+
+const YEAR: Flag = option("--year", "year", "Use this event instead of AOC_YEAR");
+
+const SUBMIT: Flag = Flag {
+    choices: &["1", "2"],
+    ..option(
+        "--submit",
+        "part",
+        "Submit the answer to part 1 or 2 via aoc-cli",
+    )
+};
+const TIMEOUT: Flag = option(
+    "--timeout",
+    "seconds",
+    "Stop a part after this many seconds",
+);
+const MEMORY: Flag = option(
+    "--memory",
+    "MiB",
+    "Stop a part once its peak memory exceeds this many MiB",
+);
+const INPUT: Flag = option(
+    "--input",
+    PATH,
+    "Read the input from a file instead, or from stdin for -",
+);
+const INPUT_SET: Flag = option(
+    "--input-set",
+    "name",
+    "Read the input from data/inputs/<name> and check the answers recorded there",
+);
+const EXAMPLE: Flag = Flag {
+    optional: true,
+    ..option(
+        "--example",
+        "n",
+        "Read the input from data/examples, or its nth numbered file",
+    )
+};
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "all",
+        about: "Run the solutions of all days",
+        positional: Some(days(false)),
+        flags: &[
+            switch("--release", "Compile with optimizations"),
+            switch("--time", "Benchmark the parts and update the readme"),
+            TIMEOUT,
+            MEMORY,
+            option("--jobs", "n", "Run this many days in parallel"),
+            option(
+                "--columns",
+                "list",
+                "Extra readme columns: parse, samples, stddev, share, chart, links, heap or all",
+            ),
+            Flag {
+                choices: &["markdown", "html"],
+                ..option("--format", "format", "Format of the readme table")
+            },
+            switch(
+                "--metadata",
+                "Add machine, rustc and commit below the readme table",
+            ),
+            option("--svg", PATH, "Also render the timings as an SVG chart"),
+            option(
+                "--export",
+                PATH,
+                "Write the timings to a .json or .csv file",
+            ),
+            switch("--heap", "Measure heap usage"),
+        ],
+    },
+    Command {
+        name: "auth",
+        about: "Manage the session cookies of adventofcode.com accounts",
+        positional: Some(Positional {
+            name: "action",
+            help: "One of set, show, validate, clear or list",
+            required: true,
+            choices: &["set", "show", "validate", "clear", "list"],
+        }),
+        flags: &[option(
+            "--profile",
+            "name",
+            "Use this profile instead of the one in AOC_SESSION_PROFILE",
+        )],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script",
+        positional: Some(Positional {
+            name: "shell",
+            help: "One of bash, zsh or fish",
+            required: true,
+            choices: &["bash", "zsh", "fish"],
+        }),
+        flags: &[],
+    },
+    Command {
+        name: "crosscheck",
+        about: "Check solutions against the inputs and answers of every input set",
+        positional: Some(days(false)),
+        flags: &[
+            switch("--release", "Compile with optimizations"),
+            option("--input-set", "name", "Only check this input set"),
+        ],
+    },
+    Command {
+        name: "decrypt",
+        about: "Write the plain text inputs of days from their encrypted files",
+        positional: Some(days(false)),
+        flags: &[],
+    },
+    Command {
+        name: "download",
+        about: "Download the input and puzzle description of days",
+        positional: Some(days(false)),
+        flags: &[
+            switch("--all", "Download every unlocked day of the event"),
+            option(
+                "--year",
+                "year",
+                "Download from this event instead of AOC_YEAR",
+            ),
+            switch("--wait", "Wait until the puzzle unlocks"),
+            switch(
+                "--refresh",
+                "Fetch the puzzle description again instead of using the cache",
+            ),
+        ],
+    },
+    Command {
+        name: "encrypt",
+        about: "Encrypt the inputs of days, so that they can be committed",
+        positional: Some(days(false)),
+        flags: &[],
+    },
+    Command {
+        name: "leaderboard",
+        about: "Show a private leaderboard",
+        positional: Some(Positional {
+            name: "id",
+            help: "The id of the leaderboard, the number at the end of its URL",
+            required: false,
+            choices: &[],
+        }),
+        flags: &[
+            option(
+                "--file",
+                PATH,
+                "Read the leaderboard JSON from a file instead",
+            ),
+            YEAR,
+            option("--day", "day", "Show the times of one day"),
+        ],
+    },
+    Command {
+        name: "profile",
+        about: "Record a flame graph of a solution with perf",
+        positional: Some(day()),
+        flags: &[
+            Flag {
+                choices: &["1", "2"],
+                ..option("--part", "part", "Only profile part 1 or 2")
+            },
+            option("--frequency", "hz", "Samples per second"),
+            option(
+                "--duration",
+                "seconds",
+                "Run the part repeatedly for this long, at least once",
+            ),
+        ],
+    },
+    Command {
+        name: "read",
+        about: "Read the puzzle description of a day in the terminal",
+        positional: Some(day()),
+        flags: &[Flag {
+            choices: &["1", "2"],
+            ..option("--part", "part", "Only show part 1 or 2")
+        }],
+    },
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files of days",
+        positional: Some(days(true)),
+        flags: &[],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of days",
+        positional: Some(days(true)),
+        flags: &[
+            switch("--release", "Compile with optimizations"),
+            switch("--time", "Benchmark the parts"),
+            switch("--trace", "Print trace! messages and record frames"),
+            SUBMIT,
+            INPUT,
+            EXAMPLE,
+            INPUT_SET,
+            TIMEOUT,
+            MEMORY,
+            switch("--heap", "Measure heap usage"),
+            option("--variant", "name", "Run a named variant of the parts"),
+            switch(
+                "--all-variants",
+                "Run and compare every variant of the parts",
+            ),
+        ],
+    },
+    Command {
+        name: "status",
+        about: "Show the progress of days",
+        positional: Some(days(false)),
+        flags: &[switch("--no-tests", "Don't run the tests of solutions")],
+    },
+    Command {
+        name: "view",
+        about: "Step through the frames recorded with --trace",
+        positional: Some(day()),
+        flags: &[],
+    },
+];
+
+/// The arguments of a day binary, which `solve`, `all` and the runner pass on. Not part of the completions.
+pub const SOLUTION: Command = Command {
+    name: "solution",
+    about: "Run the parts of a day",
+    positional: None,
+    flags: &[
+        switch("--time", "Benchmark the parts"),
+        switch("--trace", "Print trace! messages and record frames"),
+        SUBMIT,
+        INPUT,
+        EXAMPLE,
+        INPUT_SET,
+        Flag {
+            choices: &["1", "2"],
+            ..option("--part", "part", "Only run part 1 or 2")
+        },
+        TIMEOUT,
+        MEMORY,
+        option(
+            "--duration",
+            "seconds",
+            "Run the parts repeatedly for this long, at least once",
+        ),
+        switch("--raw-samples", "Print every benchmark sample"),
+        switch(
+            "--heap-stats",
+            "Print heap usage in a machine readable format",
+        ),
+        option("--variant", "name", "Run a named variant of the parts"),
+        switch(
+            "--all-variants",
+            "Run and compare every variant of the parts",
+        ),
+    ],
+};
+
+#[must_use]
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    UnknownCommand(String),
+    UnknownArgument(String),
+    MissingValue(&'static str),
+    UnexpectedValue(&'static str),
+    MissingArgument(&'static str),
+    Conflict(&'static str, &'static str),
+    InvalidValue {
+        name: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownCommand(name) => write!(f, "unknown command \"{name}\""),
+            Error::UnknownArgument(arg) => write!(f, "unknown argument \"{arg}\""),
+            Error::MissingValue(flag) => write!(f, "{flag} requires a value"),
+            Error::UnexpectedValue(flag) => write!(f, "{flag} does not take a value"),
+            Error::MissingArgument(name) => write!(f, "missing argument <{name}>"),
+            Error::Conflict(a, b) => write!(f, "{a} cannot be used with {b}"),
+            Error::InvalidValue {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid value \"{value}\" for {name}: {reason}"),
+        }
+    }
+}
+
+/// An error which is returned when a part is neither 1 nor 2.
+#[derive(Debug)]
+pub struct InvalidPartError;
+
+impl error::Error for InvalidPartError {}
+
+impl Display for InvalidPartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("part must be 1 or 2")
+    }
+}
+
+/// Parses a part number, e.g. the value of `--submit`.
+pub fn parse_part(s: &str) -> Result<u8, InvalidPartError> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(InvalidPartError),
+    }
+}
+
+/// An error which is returned when a duration is not a positive number of seconds.
+#[derive(Debug)]
+pub struct InvalidSecsError;
+
+impl error::Error for InvalidSecsError {}
+
+impl Display for InvalidSecsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expecting a positive number of seconds")
+    }
+}
+
+/// Parses a duration in seconds, e.g. the value of `--timeout`.
+pub fn parse_secs(s: &str) -> Result<Duration, InvalidSecsError> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|secs| *secs > 0.0)
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or(InvalidSecsError)
+}
+
+/// The arguments of a command, checked against its definition.
+#[derive(Debug, Default)]
+pub struct Matches {
+    values: BTreeMap<&'static str, Option<String>>,
+    free: Option<String>,
+    /// Whether `--help` was passed, in which case the other arguments are not checked.
+    pub help: bool,
+}
+
+impl Matches {
+    #[must_use]
+    pub fn contains(&self, flag: &str) -> bool {
+        self.values.contains_key(flag)
+    }
+
+    #[must_use]
+    pub fn value(&self, flag: &str) -> Option<&str> {
+        self.values.get(flag).and_then(Option::as_deref)
+    }
+
+    pub fn value_from_fn<T, E: Display>(
+        &self,
+        flag: &str,
+        f: impl Fn(&str) -> Result<T, E>,
+    ) -> Result<Option<T>, Error> {
+        self.value(flag)
+            .map(|value| f(value).map_err(|e| invalid_value(flag, value, &e)))
+            .transpose()
+    }
+
+    pub fn value_from_str<T: FromStr>(&self, flag: &str) -> Result<Option<T>, Error>
+    where
+        T::Err: Display,
+    {
+        self.value_from_fn(flag, str::parse)
+    }
+
+    pub fn free_from_fn<T, E: Display>(
+        &self,
+        f: impl Fn(&str) -> Result<T, E>,
+    ) -> Result<Option<T>, Error> {
+        self.free
+            .as_deref()
+            .map(|value| f(value).map_err(|e| invalid_value("the argument", value, &e)))
+            .transpose()
+    }
+
+    /// Like [`Matches::free_from_fn`], for commands whose free argument is required.
+    pub fn required_free_from_fn<T, E: Display>(
+        &self,
+        f: impl Fn(&str) -> Result<T, E>,
+    ) -> Result<T, Error> {
+        self.free_from_fn(f)?
+            .ok_or(Error::MissingArgument("argument"))
+    }
+}
+
+fn invalid_value(name: &str, value: &str, reason: &impl Display) -> Error {
+    Error::InvalidValue {
+        name: name.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Checks `args` against the definition of `command`. Flags accept their value as the next argument or after `=`.
+pub fn parse(command: &Command, args: impl IntoIterator<Item = String>) -> Result<Matches, Error> {
+    let mut matches = Matches::default();
+    let mut args = args.into_iter().peekable();
+
+    while let Some(arg) = args.next() {
+        if arg == "--help" || arg == "-h" {
+            matches.help = true;
+            return Ok(matches);
+        }
+
+        if arg.starts_with("--") {
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+
+            let Some(flag) = command.flags.iter().find(|flag| flag.name == name) else {
+                return Err(Error::UnknownArgument(arg));
+            };
+
+            let value = match (flag.value, inline) {
+                (None, None) => None,
+                (None, Some(_)) => return Err(Error::UnexpectedValue(flag.name)),
+                (Some(_), Some(value)) => Some(value),
+                (Some(_), None) if flag.optional => args.next_if(|next| !next.starts_with('-')),
+                (Some(_), None) => Some(args.next().ok_or(Error::MissingValue(flag.name))?),
+            };
+
+            matches.values.insert(flag.name, value);
+            continue;
+        }
+
+        match &command.positional {
+            Some(_) if matches.free.is_none() => matches.free = Some(arg),
+            _ => return Err(Error::UnknownArgument(arg)),
+        }
+    }
+
+    if let Some(positional) = &command.positional {
+        if positional.required && matches.free.is_none() {
+            return Err(Error::MissingArgument(positional.name));
+        }
+    }
+
+    Ok(matches)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn flag_usage(flag: &Flag) -> String {
+    match flag.value {
+        Some(value) if flag.optional => format!("{} [{value}]", flag.name),
+        Some(value) => format!("{} <{value}>", flag.name),
+        None => flag.name.to_string(),
+    }
+}
+
+fn write_rows(out: &mut String, rows: &[(String, &str)]) {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, help) in rows {
+        let _ = writeln!(out, "  {name:<width$}  {help}");
+    }
+}
+
+/// The overview printed by `cargo <command> --help` for the template itself.
+#[must_use]
+pub fn app_help() -> String {
+    let mut out = String::from("Usage: cargo <command> [arguments]\n\nCommands:\n");
+    let rows: Vec<_> = COMMANDS
+        .iter()
+        .map(|command| (command.name.to_string(), command.about))
+        .collect();
+    write_rows(&mut out, &rows);
+    out.push_str("\nRun \"cargo <command> --help\" for the arguments of a command.\n");
+    out
+}
+
+/// The help printed by `cargo <command> --help`.
+#[must_use]
+pub fn command_help(command: &Command) -> String {
+    let mut out = format!("{}\n\nUsage: cargo {}", command.about, command.name);
+
+    if let Some(positional) = &command.positional {
+        if positional.required {
+            let _ = write!(out, " <{}>", positional.name);
+        } else {
+            let _ = write!(out, " [{}]", positional.name);
+        }
+    }
+    if !command.flags.is_empty() {
+        out.push_str(" [options]");
+    }
+    out.push('\n');
+
+    if let Some(positional) = &command.positional {
+        out.push_str("\nArguments:\n");
+        write_rows(
+            &mut out,
+            &[(format!("<{}>", positional.name), positional.help)],
+        );
+    }
+
+    out.push_str("\nOptions:\n");
+    let mut rows: Vec<_> = command
+        .flags
+        .iter()
+        .map(|flag| (flag_usage(flag), flag.help))
+        .collect();
+    rows.push(("-h, --help".to_string(), "Print help"));
+    write_rows(&mut out, &rows);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// An error which is returned when generating completions for an unsupported shell.
+#[derive(Debug)]
+pub struct UnknownShellError(String);
+
+impl error::Error for UnknownShellError {}
+
+impl Display for UnknownShellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown shell \"{}\", expected bash, zsh or fish",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Shell {
+    type Err = UnknownShellError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(UnknownShellError(s.into())),
+        }
+    }
+}
+
+/// A completion script for the `cargo <command>` aliases. Other cargo subcommands fall back to cargo's own completion.
+#[must_use]
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn command_names() -> String {
+    COMMANDS
+        .iter()
+        .map(|command| command.name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bash_completions() -> String {
+    let mut out = String::from(
+        "# bash completion for the advent-of-code cargo aliases.\n\
+         # Load cargo's own completion first, so that it can be used for other subcommands.\n\
+         declare -F _completion_loader >/dev/null && _completion_loader cargo >/dev/null 2>&1\n\n\
+         _advent_of_code() {\n    \
+         local cur=\"${COMP_WORDS[COMP_CWORD]}\" prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    COMPREPLY=()\n\n    \
+         if [[ $COMP_CWORD -gt 1 ]]; then\n        \
+         case \"${COMP_WORDS[1]}\" in\n",
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(out, "            {})", command.name);
+        out.push_str("                case \"$prev\" in\n");
+        for flag in command
+            .flags
+            .iter()
+            .filter(|flag| flag.value.is_some() && !flag.optional)
+        {
+            let reply = if flag.value == Some(PATH) {
+                "compgen -f -- \"$cur\"".to_string()
+            } else {
+                format!("compgen -W \"{}\" -- \"$cur\"", flag.choices.join(" "))
+            };
+            let _ = writeln!(
+                out,
+                "                    {}) COMPREPLY=($({reply})); return ;;",
+                flag.name
+            );
+        }
+        out.push_str("                esac\n");
+
+        let words: Vec<_> = command
+            .flags
+            .iter()
+            .map(|flag| flag.name)
+            .chain(
+                command
+                    .positional
+                    .iter()
+                    .flat_map(|p| p.choices.iter().copied()),
+            )
+            .chain(["--help"])
+            .collect();
+        let _ = writeln!(
+            out,
+            "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+            words.join(" ")
+        );
+    }
+
+    let _ = write!(
+        out,
+        "        esac\n    fi\n\n    \
+         declare -F _cargo >/dev/null && _cargo \"$@\"\n    \
+         if [[ $COMP_CWORD -eq 1 ]]; then\n        \
+         COMPREPLY+=($(compgen -W \"{}\" -- \"$cur\"))\n    \
+         fi\n\
+         }}\n\n\
+         complete -o default -F _advent_of_code cargo\n",
+        command_names()
+    );
+    out
+}
+
+/// Escapes the characters that are special in the descriptions of zsh's `_arguments`.
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+fn zsh_completions() -> String {
+    let mut out = String::from(
+        "#compdef cargo\n\
+         # zsh completion for the advent-of-code cargo aliases.\n\n\
+         _advent_of_code() {\n    \
+         if (( CURRENT > 2 )); then\n        \
+         case $words[2] in\n",
+    );
+
+    for command in COMMANDS {
+        let _ = writeln!(out, "            {})", command.name);
+        out.push_str("                shift words; (( CURRENT-- ))\n");
+        out.push_str("                _arguments \\\n");
+
+        if let Some(positional) = &command.positional {
+            let _ = writeln!(
+                out,
+                "                    '1:{}:({})' \\",
+                positional.name,
+                positional.choices.join(" ")
+            );
+        }
+
+        for flag in command.flags {
+            let action = match flag.value {
+                None => String::new(),
+                Some(PATH) => format!(":{PATH}:_files"),
+                Some(value) if flag.optional => format!("::{value}: "),
+                Some(value) if flag.choices.is_empty() => format!(":{value}: "),
+                Some(value) => format!(":{value}:({})", flag.choices.join(" ")),
+            };
+            let _ = writeln!(
+                out,
+                "                    '{}[{}]{action}' \\",
+                flag.name,
+                zsh_escape(flag.help)
+            );
+        }
+
+        out.push_str("                    '(-h --help)'{-h,--help}'[Print help]'\n");
+        out.push_str("                return ;;\n");
+    }
+
+    out.push_str(
+        "        esac\n    fi\n\n    \
+         if (( CURRENT == 2 )); then\n        \
+         local -a commands=(\n",
+    );
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "            '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        );
+    }
+    out.push_str(
+        "        )\n        \
+         _describe 'command' commands\n    \
+         fi\n\n    \
+         (( $+functions[_cargo] )) && _cargo \"$@\"\n\
+         }\n\n\
+         compdef _advent_of_code cargo\n",
+    );
+    out
+}
+
+fn fish_completions() -> String {
+    let mut out = String::from("# fish completion for the advent-of-code cargo aliases.\n");
+
+    for command in COMMANDS {
+        let _ = writeln!(
+            out,
+            "complete -c cargo -n '__fish_use_subcommand' -f -a {} -d '{}'",
+            command.name,
+            command.about.replace('\'', "\\'")
+        );
+    }
+
+    for command in COMMANDS {
+        let condition = format!("__fish_seen_subcommand_from {}", command.name);
+
+        if let Some(positional) = command
+            .positional
+            .as_ref()
+            .filter(|p| !p.choices.is_empty())
+        {
+            let _ = writeln!(
+                out,
+                "complete -c cargo -n '{condition}' -f -a '{}'",
+                positional.choices.join(" ")
+            );
+        }
+
+        for flag in command.flags {
+            let mut line = format!(
+                "complete -c cargo -n '{condition}' -l {}",
+                flag.name.trim_start_matches("--")
+            );
+            match flag.value {
+                None => {}
+                Some(PATH) => line.push_str(" -r -F"),
+                Some(_) if flag.optional => line.push_str(" -f"),
+                Some(_) if flag.choices.is_empty() => line.push_str(" -x"),
+                Some(_) => {
+                    let _ = write!(line, " -x -a '{}'", flag.choices.join(" "));
+                }
+            }
+            let _ = writeln!(out, "{line} -d '{}'", flag.help.replace('\'', "\\'"));
+        }
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn solve() -> &'static Command {
+        find_command("solve").unwrap()
+    }
+
+    #[test]
+    fn parses_flags_and_free_argument() {
+        let matches = parse(solve(), args("1-3 --release --submit 2 --variant=scan")).unwrap();
+        assert!(matches.contains("--release"));
+        assert!(!matches.contains("--time"));
+        assert_eq!(matches.value("--submit"), Some("2"));
+        assert_eq!(matches.value("--variant"), Some("scan"));
+        assert_eq!(
+            matches.free_from_fn(str::parse::<String>).unwrap(),
+            Some("1-3".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert_eq!(
+            parse(solve(), args("1 --relase")).unwrap_err(),
+            Error::UnknownArgument("--relase".into())
+        );
+        assert_eq!(
+            parse(solve(), args("1 2")).unwrap_err(),
+            Error::UnknownArgument("2".into())
+        );
+        assert_eq!(
+            parse(solve(), args("1 --release=yes")).unwrap_err(),
+            Error::UnexpectedValue("--release")
+        );
+    }
+
+    #[test]
+    fn rejects_missing_values() {
+        assert_eq!(
+            parse(solve(), args("1 --submit")).unwrap_err(),
+            Error::MissingValue("--submit")
+        );
+        assert_eq!(
+            parse(solve(), args("--time")).unwrap_err(),
+            Error::MissingArgument("days")
+        );
+    }
+
+    #[test]
+    fn validates_parts() {
+        let matches = parse(solve(), args("1 --submit 3")).unwrap();
+        assert_eq!(
+            matches.value_from_fn("--submit", parse_part).unwrap_err(),
+            Error::InvalidValue {
+                name: "--submit".into(),
+                value: "3".into(),
+                reason: "part must be 1 or 2".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_secs() {
+        assert_eq!(parse_secs("2.5").unwrap(), Duration::from_millis(2500));
+        for invalid in ["0", "-1", "NaN", "inf", "1e100", "soon"] {
+            assert!(parse_secs(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn parses_optional_values() {
+        let matches = parse(solve(), args("1 --example 2")).unwrap();
+        assert_eq!(matches.value("--example"), Some("2"));
+
+        let matches = parse(solve(), args("1 --example --release")).unwrap();
+        assert!(matches.contains("--example"));
+        assert_eq!(matches.value("--example"), None);
+        assert!(matches.contains("--release"));
+
+        assert_eq!(
+            parse(solve(), args("--example 1")).unwrap_err(),
+            Error::MissingArgument("days")
+        );
+    }
+
+    #[test]
+    fn stops_at_help() {
+        assert!(parse(solve(), args("--help --unknown")).unwrap().help);
+        assert!(parse(solve(), args("-h")).unwrap().help);
+    }
+
+    #[test]
+    fn generates_help() {
+        let help = command_help(solve());
+        assert!(
+            help.starts_with("Run the solution of days\n\nUsage: cargo solve <days> [options]\n")
+        );
+        assert!(
+            help.contains("  --submit <part>      Submit the answer to part 1 or 2 via aoc-cli\n")
+        );
+        assert!(help.contains("  -h, --help           Print help\n"));
+
+        for command in COMMANDS {
+            assert!(app_help().contains(command.about));
+        }
+    }
+
+    #[test]
+    fn generates_completions() {
+        let bash = completions(Shell::Bash);
+        assert!(bash.contains("--submit) COMPREPLY=($(compgen -W \"1 2\" -- \"$cur\")); return ;;"));
+        assert!(bash.contains("complete -o default -F _advent_of_code cargo"));
+
+        let zsh = completions(Shell::Zsh);
+        assert!(zsh.contains("'--submit[Submit the answer to part 1 or 2 via aoc-cli]:part:(1 2)'"));
+        assert!(zsh.contains("'1:shell:(bash zsh fish)'"));
+
+        let fish = completions(Shell::Fish);
+        assert!(fish.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -l submit -x -a '1 2'"
+        ));
+        assert!(fish.contains("-l export -r -F"));
+    }
+}
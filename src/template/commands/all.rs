@@ -1,39 +1,107 @@
 use std::io;
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    budget::Budget,
+    export::{self, Export},
+    readme_benchmarks::{self, Metadata, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: DaySet,
+    is_release: bool,
+    is_timed: bool,
+    budget: Budget,
+    jobs: usize,
+    readme: &readme_benchmarks::Config,
+    export: Option<&Export>,
+    heap: bool,
+) {
+    if days.is_empty() {
+        println!("No days selected.");
+        return;
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let first_day = days.iter().next();
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
-
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
-
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
+    let mut collect = |day: Day, output: &[String]| {
         if output.is_empty() {
             println!("Not solved.");
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
+            let val = child_commands::parse_exec_time(output, day);
             timings.push(val);
         }
-    });
+    };
+
+    if export.is_some() && !is_timed {
+        println!("{ANSI_ITALIC}Nothing to export without --time.{ANSI_RESET}\n");
+    }
+
+    let raw_samples = is_timed && export.is_some();
+
+    if jobs > 1 && !is_timed {
+        // build once up front so that child commands don't wait on each other for the build lock.
+        child_commands::build_solutions(is_release, heap);
+
+        parallel::run_in_order(
+            days.iter().collect(),
+            jobs,
+            |day| {
+                child_commands::run_solution(day, is_timed, is_release, budget, false, false, heap)
+                    .unwrap()
+            },
+            |day, output| {
+                print_header(day, Some(day) == first_day);
+                output.print();
+                collect(day, &output.stdout);
+            },
+        );
+    } else {
+        if jobs > 1 {
+            println!("{ANSI_ITALIC}Timed runs are sequential, ignoring --jobs.{ANSI_RESET}\n");
+        }
+
+        days.iter().for_each(|day| {
+            print_header(day, Some(day) == first_day);
+            let output = child_commands::run_solution(
+                day,
+                is_timed,
+                is_release,
+                budget,
+                true,
+                raw_samples,
+                heap,
+            )
+            .unwrap();
+            collect(day, &output.stdout);
+        });
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        if let Some(export) = export {
+            match export::write(
+                export,
+                &timings,
+                total_millis * 1_000_000_f64,
+                &Metadata::collect(),
+            ) {
+                Ok(()) => println!("Exported results to {}.", export.path.display()),
+                Err(e) => eprintln!("Failed to export results: {e}"),
+            }
+        }
+
+        // the table is written as a whole, a selection of days would drop the rows of the other days.
+        if is_release && days != DaySet::all() {
+            println!("Not updating README with benchmarks, as only some days ran.");
+        } else if is_release {
+            match readme_benchmarks::update(timings, total_millis, readme) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,6 +111,15 @@ pub fn handle(is_release: bool, is_timed: bool) {
     }
 }
 
+fn print_header(day: Day, is_first: bool) {
+    if !is_first {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
@@ -61,10 +138,96 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Runs days concurrently while handing their results back in day order.
+mod parallel {
+    use crate::Day;
+    use std::{
+        collections::BTreeMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc,
+        },
+        thread,
+    };
+
+    /// Runs `run` for every day on up to `jobs` threads.
+    /// `done` is called on the calling thread, in the order of `days`, as soon as a day and all days before it finished.
+    pub fn run_in_order<T: Send>(
+        days: Vec<Day>,
+        jobs: usize,
+        run: impl Fn(Day) -> T + Sync,
+        mut done: impl FnMut(Day, T),
+    ) {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(days.len()) {
+                let sender = sender.clone();
+                let (days, next, run) = (&days, &next, &run);
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(day) = days.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, run(*day))).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(sender);
+
+            let mut pending = BTreeMap::new();
+            let mut expected = 0;
+
+            for (index, result) in receiver {
+                pending.insert(index, result);
+                while let Some(result) = pending.remove(&expected) {
+                    done(days[expected], result);
+                    expected += 1;
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::run_in_order;
+        use crate::{all_days, day_count};
+        use std::{thread, time::Duration};
+
+        #[test]
+        fn hands_back_results_in_order() {
+            let mut seen = vec![];
+
+            run_in_order(
+                all_days().collect(),
+                4,
+                |day| {
+                    // later days finish first.
+                    thread::sleep(Duration::from_millis(u64::from(32 - day.into_inner())));
+                    day.into_inner() * 2
+                },
+                |day, result| seen.push((day.into_inner(), result)),
+            );
+
+            assert_eq!(
+                seen,
+                (1..=day_count()).map(|d| (d, d * 2)).collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::budget::{Budget, OUT_OF_MEMORY, TIMED_OUT};
+    use crate::template::heap::{HeapStats, HEAP_FEATURE};
+    use crate::template::readme_benchmarks::Stats;
+    use crate::template::runner::{HEAP_STATS, RAW_SAMPLES};
+    use crate::template::ANSI_BOLD;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -73,28 +236,98 @@ mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Output lines of a solution bin.
+    pub struct SolutionOutput {
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+    }
+
+    impl SolutionOutput {
+        /// Prints output that was captured instead of forwarded.
+        pub fn print(&self) {
+            self.stdout
+                .iter()
+                .filter(|line| !is_hidden(line))
+                .for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+        }
+    }
+
+    /// Lines that are only meant for parsing, see [`RAW_SAMPLES`] and [`HEAP_STATS`].
+    fn is_hidden(line: &str) -> bool {
+        line.contains(RAW_SAMPLES) || line.contains(HEAP_STATS)
+    }
+
+    /// Build all solution bins, so that they can be run concurrently.
+    pub fn build_solutions(is_release: bool, heap: bool) {
+        let mut args = vec!["build", "--quiet", "--bins"];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        if heap {
+            args.extend(["--features", HEAP_FEATURE]);
+        }
+
+        if let Err(e) = Command::new("cargo").args(&args).status() {
+            eprintln!("Failed to build solutions: {e}");
+        }
+    }
+
+    /// Run the solution bin for a given day.
+    /// With `forward`, output is printed while it is captured. Otherwise it is only captured.
+    /// With `raw_samples`, the bin prints every benchmark sample. These lines are captured, but never printed.
+    /// With `heap`, the bin is built with the counting allocator and reports heap usage.
+    #[allow(clippy::fn_params_excessive_bools)]
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        budget: Budget,
+        forward: bool,
+        raw_samples: bool,
+        heap: bool,
+    ) -> Result<SolutionOutput, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(SolutionOutput {
+                stdout: vec![],
+                stderr: vec![],
+            });
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args: Vec<String> = vec!["run".into(), "--quiet".into(), "--bin".into()];
+        args.push(day.to_string());
 
         if is_release {
-            args.push("--release");
+            args.push("--release".into());
         }
 
+        if heap {
+            args.push("--features".into());
+            args.push(HEAP_FEATURE.into());
+        }
+
+        args.push("--".into());
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
+        }
+
+        if raw_samples {
+            args.push("--raw-samples".into());
         }
 
+        if heap {
+            args.push("--heap-stats".into());
+        }
+
+        args.extend(budget.to_args());
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing the lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,21 +341,33 @@ mod child_commands {
         let mut output = vec![];
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            stderr
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    if forward {
+                        eprintln!("{line}");
+                    }
+                    line
+                })
+                .collect()
         });
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if forward && !is_hidden(&line) {
+                println!("{line}");
+            }
             output.push(line);
         }
 
-        thread.join().unwrap();
+        let stderr = thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(SolutionOutput {
+            stdout: output,
+            stderr,
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -130,9 +375,57 @@ mod child_commands {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
+            stats: [None, None],
+            heap: [None, None],
             total_nanos: 0_f64,
         };
 
+        let mut raw_samples = [None, None, None];
+
+        for line in output {
+            for (i, label) in ["Parse", "Part 1", "Part 2"].iter().enumerate() {
+                if let Some(samples) = line
+                    .strip_prefix(label)
+                    .and_then(|l| l.strip_prefix(&format!(" {RAW_SAMPLES}: ")))
+                {
+                    raw_samples[i] = Some(
+                        samples
+                            .split_whitespace()
+                            .filter_map(|x| x.parse().ok())
+                            .collect(),
+                    );
+                }
+            }
+
+            for (i, label) in ["Part 1", "Part 2"].iter().enumerate() {
+                if let Some(stats) = line
+                    .strip_prefix(label)
+                    .and_then(|l| l.strip_prefix(&format!(" {HEAP_STATS}: ")))
+                {
+                    timings.heap[i] = parse_heap_stats(stats);
+                }
+            }
+
+            // the final status overwrites intermediate output on the same line.
+            let line = line.rsplit('\r').next().unwrap_or(line);
+            for (part, timing) in [
+                ("Part 1", &mut timings.part_1),
+                ("Part 2", &mut timings.part_2),
+            ] {
+                let Some(rest) = line.strip_prefix(part).and_then(|l| l.strip_prefix(": ")) else {
+                    continue;
+                };
+                let rest = rest.replace(ANSI_BOLD, "");
+                if let Some(status) = [TIMED_OUT, OUT_OF_MEMORY]
+                    .iter()
+                    .find(|s| rest.starts_with(*s))
+                {
+                    *timing = Some((*status).into());
+                }
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -140,50 +433,91 @@ mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(stats) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, stats))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+            .for_each(|(part, stats)| {
+                let index = if part.contains("Part 1") {
+                    0
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+                    1
+                } else {
+                    // parsing is timed separately, but is also part of both parts.
+                    if part.contains("Parse") {
+                        timings.parse = Some(stats);
+                    }
+                    return;
+                };
 
-                timings.total_nanos += nanos;
+                [&mut timings.part_1, &mut timings.part_2][index].replace(stats.time.clone());
+                timings.total_nanos += stats.nanos;
+                timings.stats[index] = Some(stats);
             });
 
+        let [parse, part_1, part_2] = raw_samples;
+        let [stats_1, stats_2] = &mut timings.stats;
+        for (stats, raw) in [&mut timings.parse, stats_1, stats_2]
+            .into_iter()
+            .zip([parse, part_1, part_2])
+        {
+            if let (Some(stats), Some(raw)) = (stats, raw) {
+                stats.raw_nanos = raw;
+            }
+        }
+
         timings
     }
 
+    /// Parses `<peak bytes> <allocations> <allocated bytes>`.
+    fn parse_heap_stats(s: &str) -> Option<HeapStats> {
+        let mut values = s.split_whitespace().map(str::parse);
+        Some(HeapStats {
+            peak_bytes: values.next()?.ok()?,
+            allocations: values.next()?.ok()?,
+            allocated_bytes: values.next()?.ok()?,
+        })
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    fn parse_duration(s: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        }
+    }
+
+    /// Parses `(<mean> ± <stddev> @ <n> samples)`. The standard deviation is optional.
+    fn parse_time(line: &str) -> Option<Stats> {
+        let (timing, samples) = line
+            .split(" samples)")
+            .next()?
+            .split('(')
+            .next_back()?
+            .split_once('@')?;
 
-        Some((str_timing, parsed_timing))
+        let (mean, stddev) = match timing.split_once('±') {
+            Some((mean, stddev)) => (mean.trim(), parse_duration(stddev.trim())?),
+            None => (timing.trim(), 0_f64),
+        };
+
+        Some(Stats {
+            time: mean.into(),
+            nanos: parse_duration(mean)?,
+            stddev_nanos: stddev,
+            samples: samples.trim().parse().ok()?,
+            raw_nanos: vec![],
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -236,6 +570,81 @@ mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn test_stddev_and_parse() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: \rParse: (1.5µs ± 200.0ns @ 5000 samples)".into(),
+                    "Part 1: 0 (74.13ns ± 1.00ns @ 100000 samples)".into(),
+                    "Part 2: 10 > benching\rPart 2: 10 (2.00ms ± 0.10ms @ 500 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_2.unwrap(), "2.00ms");
+
+            let parse = res.parse.unwrap();
+            assert_eq!(parse.time, "1.5µs");
+            assert_approx_eq!(parse.stddev_nanos, 200_f64);
+            assert_eq!(parse.samples, 5000);
+
+            let part_2 = res.stats[1].as_ref().unwrap();
+            assert_approx_eq!(part_2.stddev_nanos, 100000_f64);
+            assert_eq!(part_2.samples, 500);
+        }
+
+        #[test]
+        fn test_raw_samples() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (2.0ns ± 0.8ns @ 3 samples)".into(),
+                    "Part 1 raw samples: 3 1 2".into(),
+                    "Part 2: ✖        ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.stats[0].as_ref().unwrap().raw_nanos, vec![3, 1, 2]);
+            assert!(res.stats[1].is_none());
+        }
+
+        #[test]
+        fn test_heap_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (2.0ns @ 3 samples) [peak 1.0 KiB, 2 allocations, 1.5 KiB allocated]"
+                        .into(),
+                    "Part 1 heap stats: 1024 2 1536".into(),
+                    "Part 2: 0 (2.0ns @ 3 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let heap = res.heap[0].unwrap();
+            assert_eq!(heap.peak_bytes, 1024);
+            assert_eq!(heap.allocations, 2);
+            assert_eq!(heap.allocated_bytes, 1536);
+            assert!(res.heap[1].is_none());
+            assert_eq!(res.part_1.unwrap(), "2.0ns");
+        }
+
+        #[test]
+        fn test_exceeded_budgets() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: \x1b[1mtimed out\x1b[0m (> 1.0s)".into(),
+                    "Part 2: 42 > benching\rPart 2: \x1b[1mout of memory\x1b[0m (> 64 MiB)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.unwrap(), "timed out");
+            assert_eq!(res.part_2.unwrap(), "out of memory");
+        }
+
         #[test]
         fn test_missing_parts() {
             let res = parse_exec_time(
@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, stdout, BufRead, Write};
+use std::process;
+use std::str::FromStr;
+
+use crate::template::session::{self, get_path_for_session, mask};
+use crate::template::{http, ANSI_BOLD, ANSI_RESET};
+
+pub enum Action {
+    Set,
+    Show,
+    Validate,
+    Clear,
+    List,
+}
+
+/// An error which is returned for an unknown `auth` action.
+#[derive(Debug)]
+pub struct UnknownActionError(String);
+
+impl Error for UnknownActionError {}
+
+impl Display for UnknownActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown action \"{}\", expected set, show, validate, clear or list",
+            self.0
+        )
+    }
+}
+
+impl FromStr for Action {
+    type Err = UnknownActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "set" => Ok(Action::Set),
+            "show" => Ok(Action::Show),
+            "validate" => Ok(Action::Validate),
+            "clear" => Ok(Action::Clear),
+            "list" => Ok(Action::List),
+            _ => Err(UnknownActionError(s.into())),
+        }
+    }
+}
+
+fn fail(message: impl Display) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn describe_source(profile: &str) -> String {
+    if session::is_overridden(profile) {
+        format!("from {}", session::SESSION_ENV)
+    } else {
+        get_path_for_session(profile).map_or_else(String::new, |path| path.display().to_string())
+    }
+}
+
+fn read_token() -> String {
+    print!("Paste the value of the \"session\" cookie of adventofcode.com: ");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    if let Err(e) = io::stdin().lock().read_line(&mut line) {
+        fail(format!("Failed to read the session cookie: {e}"));
+    }
+
+    session::parse_token(&line).unwrap_or_else(|e| fail(format!("Invalid session cookie: {e}")))
+}
+
+fn print_validation(token: &str) -> bool {
+    match session::validate(token) {
+        Ok(true) => {
+            println!("✔ adventofcode.com accepts the session cookie.");
+            true
+        }
+        Ok(false) => {
+            println!("✖ adventofcode.com rejects the session cookie, it is invalid or expired.");
+            false
+        }
+        Err(e) => {
+            println!("? Could not validate the session cookie: {e}");
+            false
+        }
+    }
+}
+
+fn set(profile: &str) {
+    let token = read_token();
+    match session::save(profile, &token) {
+        Ok(path) => println!(
+            "Saved the session cookie of profile \"{profile}\" to \"{}\".",
+            path.display()
+        ),
+        Err(e) => fail(format!("Failed to save the session cookie: {e}")),
+    }
+
+    if session::is_overridden(profile) {
+        println!(
+            "Note: {} is set and overrides the saved cookie.",
+            session::SESSION_ENV
+        );
+    }
+
+    print_validation(&token);
+}
+
+fn show(profile: &str) {
+    let Some(token) = session::load(profile) else {
+        fail(format!(
+            "No session cookie is set for profile \"{profile}\"."
+        ));
+    };
+
+    println!("{profile}: {} ({})", mask(&token), describe_source(profile));
+}
+
+fn validate(profile: &str) {
+    let Some(token) = session::load(profile) else {
+        fail(http::Error::MissingSession);
+    };
+
+    if !print_validation(&token) {
+        process::exit(1);
+    }
+}
+
+fn clear(profile: &str) {
+    match session::clear(profile) {
+        Ok(true) => println!("Removed the session cookie of profile \"{profile}\"."),
+        Ok(false) => println!("No session cookie is saved for profile \"{profile}\"."),
+        Err(e) => fail(format!("Failed to remove the session cookie: {e}")),
+    }
+}
+
+fn list() {
+    let active = session::active_profile();
+    let mut profiles = session::list();
+    if session::is_overridden(&active) && !profiles.contains(&active) {
+        profiles.push(active.clone());
+        profiles.sort();
+    }
+
+    if profiles.is_empty() {
+        println!("No session cookies are saved. Run \"cargo auth set\" to add one.");
+        return;
+    }
+
+    for profile in profiles {
+        let token = session::load(&profile).unwrap_or_default();
+        let line = format!(
+            "{profile}: {} ({})",
+            mask(&token),
+            describe_source(&profile)
+        );
+        if profile == active {
+            println!("{ANSI_BOLD}* {line}{ANSI_RESET}");
+        } else {
+            println!("  {line}");
+        }
+    }
+}
+
+pub fn handle(action: &Action, profile: Option<&str>) {
+    let profile = profile.map_or_else(session::active_profile, String::from);
+
+    match action {
+        Action::Set => set(&profile),
+        Action::Show => show(&profile),
+        Action::Validate => validate(&profile),
+        Action::Clear => clear(&profile),
+        Action::List => list(),
+    }
+}
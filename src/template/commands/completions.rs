@@ -0,0 +1,5 @@
+use crate::template::cli::{completions, Shell};
+
+pub fn handle(shell: Shell) {
+    print!("{}", completions(shell));
+}
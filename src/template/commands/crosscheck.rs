@@ -0,0 +1,177 @@
+use std::path::Path;
+use std::process::{self, Command};
+
+use crate::template::input_sets::{self, Check};
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{Day, DaySet};
+
+/// The outcome of a part for one input set.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Checked(Check),
+    /// The part printed no answer, e.g. because it is unsolved or the solution panicked.
+    NoAnswer,
+}
+
+impl Outcome {
+    fn is_failure(&self) -> bool {
+        !matches!(self, Outcome::Checked(Check::Matches | Check::Missing))
+    }
+}
+
+fn build(release: bool) -> bool {
+    let mut args = vec!["build", "--quiet", "--bins"];
+    if release {
+        args.push("--release");
+    }
+
+    Command::new("cargo")
+        .args(&args)
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn run(day: Day, input_set: &str, release: bool) -> [Outcome; 2] {
+    let mut args: Vec<String> = vec!["run".into(), "--quiet".into(), "--bin".into()];
+    args.push(day.to_string());
+    if release {
+        args.push("--release".into());
+    }
+    args.extend(["--".into(), "--input-set".into(), input_set.into()]);
+
+    let stdout = Command::new("cargo")
+        .args(&args)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+
+    parse_outcomes(&stdout)
+}
+
+fn parse_outcomes(stdout: &str) -> [Outcome; 2] {
+    [1, 2].map(|part| {
+        stdout
+            .lines()
+            .find_map(|line| Check::parse(line, part))
+            .map_or(Outcome::NoAnswer, Outcome::Checked)
+    })
+}
+
+fn format_outcome(part: u8, outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Checked(Check::Matches) => format!("part {part} ✔"),
+        Outcome::Checked(Check::Differs { expected }) => {
+            format!("part {part} {ANSI_BOLD}✖ expected {expected}{ANSI_RESET}")
+        }
+        Outcome::Checked(Check::Missing) => format!("part {part} ? no recorded answer"),
+        Outcome::NoAnswer => format!("part {part} {ANSI_BOLD}✖ no answer{ANSI_RESET}"),
+    }
+}
+
+pub fn handle(days: DaySet, input_set: Option<&str>, release: bool) {
+    let sets: Vec<String> = input_sets::list()
+        .into_iter()
+        .filter(|name| input_set.is_none_or(|input_set| input_set == name))
+        .collect();
+
+    if sets.is_empty() {
+        match input_set {
+            Some(name) => eprintln!("Input set \"{name}\" not found in data/inputs."),
+            None => eprintln!("No input sets found. Put inputs in data/inputs/<name>/<day>.txt."),
+        }
+        process::exit(1);
+    }
+
+    if !build(release) {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    }
+
+    let width = sets.iter().map(String::len).max().unwrap_or(0);
+    let mut failures: Vec<(Day, &str)> = vec![];
+    let mut checked = 0;
+
+    for day in days
+        .iter()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+    {
+        let with_input: Vec<_> = sets
+            .iter()
+            .filter(|name| input_sets::has_input(name, day))
+            .collect();
+
+        if with_input.is_empty() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        for name in with_input {
+            let outcomes = run(day, name, release);
+            println!(
+                "  {name:<width$}  {}  {}",
+                format_outcome(1, &outcomes[0]),
+                format_outcome(2, &outcomes[1])
+            );
+
+            checked += 1;
+            if outcomes.iter().any(Outcome::is_failure) {
+                failures.push((day, name));
+            }
+        }
+    }
+
+    println!();
+    if checked == 0 {
+        println!("No input set has an input for the selected days.");
+        return;
+    }
+
+    if failures.is_empty() {
+        println!("{ANSI_BOLD}All {checked} inputs passed.{ANSI_RESET}");
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}{} of {checked} inputs failed:{ANSI_RESET}",
+        failures.len()
+    );
+    for (day, name) in failures {
+        println!("  day {day}: {name}");
+    }
+    process::exit(1);
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_outcomes, Outcome};
+    use crate::template::input_sets::Check;
+
+    #[test]
+    fn parses_outcomes() {
+        let stdout = [
+            "Part 1: 6\rPart 1: 6 (1.2µs)",
+            "Part 1: ✔ matches the recorded answer",
+            "Part 2: ✖\rPart 2: ✖             ",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_outcomes(&stdout),
+            [Outcome::Checked(Check::Matches), Outcome::NoAnswer]
+        );
+    }
+
+    #[test]
+    fn counts_failures() {
+        assert!(!Outcome::Checked(Check::Matches).is_failure());
+        assert!(!Outcome::Checked(Check::Missing).is_failure());
+        assert!(Outcome::Checked(Check::Differs {
+            expected: "1".into()
+        })
+        .is_failure());
+        assert!(Outcome::NoAnswer.is_failure());
+    }
+}
@@ -0,0 +1,41 @@
+use std::fs;
+use std::process;
+
+use crate::template::encryption::{self, get_path_for_encrypted, Passphrase};
+use crate::DaySet;
+
+pub fn handle(days: &DaySet) {
+    let passphrase = Passphrase::load().unwrap_or_else(|e| {
+        eprintln!("Failed to decrypt inputs: {e}");
+        process::exit(1);
+    });
+
+    let mut decrypted = 0;
+
+    for path in encryption::get_paths_for_inputs(days) {
+        let source = get_path_for_encrypted(&path);
+        let Ok(data) = fs::read(&source) else {
+            continue;
+        };
+
+        if path.exists() {
+            println!("Skipped \"{}\", it already exists.", path.display());
+            continue;
+        }
+
+        match encryption::decrypt(&data, &passphrase)
+            .and_then(|plaintext| fs::write(&path, plaintext).map_err(encryption::Error::Io))
+        {
+            Ok(()) => {
+                println!("Decrypted \"{}\".", path.display());
+                decrypted += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to decrypt \"{}\": {e}", source.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("Decrypted {decrypted} inputs.");
+}
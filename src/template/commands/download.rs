@@ -1,15 +1,282 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, process, thread};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::http::{self, Endpoint};
+use crate::template::{encryption, input, puzzle};
+use crate::{all_days_in, configured_year, Day, DaySet};
+
+/// Extra time to wait after the unlock time, in case the local clock runs slightly ahead.
+const UNLOCK_GRACE: Duration = Duration::from_secs(2);
+
+fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        (secs % 86_400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+/// Blocks until `unlock_time` has passed, displaying a countdown.
+fn wait_for_unlock(day: Day, unlock_time: SystemTime) {
+    let mut stdout = stdout();
+
+    while let Ok(remaining) = unlock_time.duration_since(SystemTime::now()) {
+        print!(
+            "\r⏳ Puzzle for day {day} unlocks in {}",
+            format_countdown(remaining)
+        );
+        let _ = stdout.flush();
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+    }
+
+    println!("\r🎄 Puzzle for day {day} is unlocked!             ");
+    thread::sleep(UNLOCK_GRACE);
+}
+
+/// Writes to a temporary file first, so that an interrupted download doesn't leave a partial input behind.
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    let partial = path.with_extension("partial");
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|()| fs::write(&partial, contents))
+        .and_then(|()| fs::rename(&partial, path))
+        .map_err(|e| format!("could not write \"{}\": {e}", path.display()))
+}
+
+/// The input and the description of a day, which are stored per event.
+fn get_paths(day: Day, year: u16) -> [PathBuf; 2] {
+    [
+        input::get_path_for_puzzle(year, day),
+        puzzle::get_path(year, day),
+    ]
+}
+
+/// Downloads the input, unless it exists. Inputs never change, so they are never downloaded again.
+fn download_input(day: Day, year: u16) -> Result<(), String> {
+    let path = input::get_path_for_puzzle(year, day);
+
+    if encryption::exists(&path) {
+        println!("🎄 Input is already present in \"{}\".", path.display());
+        return Ok(());
+    }
+
+    let input = http::get(Endpoint::Input, &puzzle::get_input_url_path(year, day))
+        .map_err(|e| e.to_string())?;
+    write(&path, &input)?;
+    println!("🎄 Successfully wrote input to \"{}\".", path.display());
+    Ok(())
+}
+
+fn download_puzzle(day: Day, year: u16, refresh: bool) -> Result<(), String> {
+    let entry = puzzle::download(year, day, refresh)?;
+    let path = puzzle::get_path(year, day);
+
+    if entry.cached {
+        println!(
+            "🎄 Wrote puzzle to \"{}\" from the cache, fetched {} ago. Pass --refresh to fetch it again.",
+            path.display(),
+            entry.age()
+        );
+    } else {
+        println!("🎄 Successfully wrote puzzle to \"{}\".", path.display());
+    }
+    Ok(())
+}
+
+/// What `download --all` does with a day.
+#[derive(Debug, PartialEq, Eq)]
+enum Plan {
+    Locked,
+    /// The input and the description are present.
+    Skip,
+    Download,
+}
+
+impl Plan {
+    fn new(unlocked: bool, present: bool, refresh: bool) -> Self {
+        match (unlocked, present && !refresh) {
+            (false, _) => Plan::Locked,
+            (true, true) => Plan::Skip,
+            (true, false) => Plan::Download,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    year: u16,
+    downloaded: usize,
+    skipped: usize,
+    locked: usize,
+}
+
+impl Summary {
+    fn lines(&self) -> Vec<String> {
+        let days = |n: usize| {
+            if n == 1 {
+                "1 day".into()
+            } else {
+                format!("{n} days")
+            }
+        };
+
+        let mut lines = vec![format!(
+            "🎄 Downloaded {} of {}.",
+            days(self.downloaded),
+            self.year
+        )];
+        if self.skipped > 0 {
+            lines.push(format!(
+                "   Skipped {} that were already present.",
+                days(self.skipped)
+            ));
+        }
+        if self.locked > 0 {
+            lines.push(format!("   {} not unlocked yet.", days(self.locked)));
+        }
+        lines
+    }
+
+    fn print(&self) {
+        println!("---");
+        for line in self.lines() {
+            println!("{line}");
+        }
+    }
+}
+
+/// Downloads every unlocked day of an event. Days with an input and a description are skipped, so running it again
+/// after an interruption resumes where it stopped.
+pub fn handle_all(year: Option<u16>, refresh: bool) {
+    let year = year.unwrap_or_else(configured_year);
+    let now = SystemTime::now();
+    let mut summary = Summary {
+        year,
+        ..Summary::default()
     };
+
+    for day in all_days_in(year) {
+        let unlocked = day.unlock_time(year) <= now;
+        let [input, description] = get_paths(day, year);
+        let present = encryption::exists(&input) && description.exists();
+
+        match Plan::new(unlocked, present, refresh) {
+            Plan::Locked => {
+                summary.locked += 1;
+                continue;
+            }
+            Plan::Skip => {
+                summary.skipped += 1;
+                continue;
+            }
+            Plan::Download => {}
+        }
+
+        if let Err(e) = download_input(day, year).and_then(|()| download_puzzle(day, year, refresh))
+        {
+            eprintln!("Failed to download day {day}: {e}");
+            summary.print();
+            eprintln!("Run the command again to resume.");
+            process::exit(1);
+        }
+        summary.downloaded += 1;
+    }
+
+    summary.print();
+}
+
+pub fn handle(days: DaySet, year: Option<u16>, wait: bool, refresh: bool) {
+    if days.is_empty() {
+        eprintln!("No days selected.");
+        process::exit(1);
+    }
+
+    let year = year.unwrap_or_else(configured_year);
+
+    for day in days {
+        let unlock_time = day.unlock_time(year);
+
+        if let Ok(remaining) = unlock_time.duration_since(SystemTime::now()) {
+            if !wait {
+                eprintln!(
+                    "Puzzle for day {day} unlocks in {}. Append --wait to download it once it unlocks.",
+                    format_countdown(remaining)
+                );
+                process::exit(1);
+            }
+            wait_for_unlock(day, unlock_time);
+        }
+
+        if let Err(e) = download_input(day, year).and_then(|()| download_puzzle(day, year, refresh))
+        {
+            eprintln!("Failed to download day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{get_paths, Plan, Summary};
+    use crate::Day;
+
+    #[test]
+    fn stores_events_apart() {
+        let day = Day::new(1).unwrap();
+        let [input, description] = get_paths(day, 2022);
+
+        assert!(input.ends_with(Path::new("data/2022/inputs/01.txt")));
+        assert!(description.ends_with(Path::new("data/2022/puzzles/01.md")));
+        assert_ne!(get_paths(day, 2023), [input, description]);
+    }
+
+    #[test]
+    fn skips_present_days() {
+        assert_eq!(Plan::new(false, false, false), Plan::Locked);
+        assert_eq!(Plan::new(false, true, true), Plan::Locked);
+        assert_eq!(Plan::new(true, false, false), Plan::Download);
+        assert_eq!(Plan::new(true, true, false), Plan::Skip);
+        assert_eq!(Plan::new(true, true, true), Plan::Download);
+    }
+
+    #[test]
+    fn summarizes_downloads() {
+        let summary = Summary {
+            year: 2025,
+            downloaded: 1,
+            skipped: 4,
+            locked: 7,
+        };
+
+        assert_eq!(
+            summary.lines(),
+            [
+                "🎄 Downloaded 1 day of 2025.",
+                "   Skipped 4 days that were already present.",
+                "   7 days not unlocked yet.",
+            ]
+        );
+        assert_eq!(
+            Summary {
+                year: 2022,
+                downloaded: 25,
+                ..Summary::default()
+            }
+            .lines(),
+            ["🎄 Downloaded 25 days of 2022."]
+        );
+    }
 }
@@ -0,0 +1,47 @@
+use std::fs;
+use std::process;
+
+use crate::template::encryption::{self, get_path_for_encrypted, Passphrase};
+use crate::DaySet;
+
+pub fn handle(days: &DaySet) {
+    let passphrase = Passphrase::load().unwrap_or_else(|e| {
+        eprintln!("Failed to encrypt inputs: {e}");
+        process::exit(1);
+    });
+
+    let mut encrypted = 0;
+    let mut unchanged = 0;
+
+    for path in encryption::get_paths_for_inputs(days) {
+        let Ok(plaintext) = fs::read(&path) else {
+            continue;
+        };
+        let target = get_path_for_encrypted(&path);
+
+        // encryption is not deterministic, keep files that are up to date to avoid noise in diffs.
+        let is_current = fs::read(&target)
+            .ok()
+            .and_then(|data| encryption::decrypt(&data, &passphrase).ok())
+            .is_some_and(|existing| existing == plaintext);
+        if is_current {
+            unchanged += 1;
+            continue;
+        }
+
+        match encryption::encrypt(&plaintext, &passphrase)
+            .and_then(|data| fs::write(&target, data).map_err(encryption::Error::Io))
+        {
+            Ok(()) => {
+                println!("Encrypted \"{}\".", target.display());
+                encrypted += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to encrypt \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("Encrypted {encrypted} inputs, {unchanged} were up to date.");
+}
@@ -0,0 +1,147 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, process};
+
+use crate::template::cache::{self, Entry};
+use crate::template::http::{self, Endpoint};
+use crate::template::leaderboard::{self, Leaderboard};
+use crate::template::{aoc_cli, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days_in, current_year, Day};
+
+/// Where the leaderboard JSON comes from.
+pub enum Source {
+    /// The id of a private leaderboard, which is the user id of its owner.
+    Fetch(u64),
+    File(PathBuf),
+}
+
+fn fail(message: impl Display) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+fn get_url_path(year: u16, id: u64) -> String {
+    format!("/{year}/leaderboard/private/view/{id}.json")
+}
+
+fn parse(json: &str) -> Leaderboard {
+    leaderboard::parse(json)
+        .unwrap_or_else(|e| fail(format!("Failed to read the leaderboard: {e}")))
+}
+
+/// Returns the cached leaderboard if it is recent, or fetches it. The replaced version is kept to show changes.
+fn fetch(id: u64, year: u16) -> (Leaderboard, Option<Leaderboard>) {
+    let path = get_url_path(year, id);
+    let previous_path = format!("{path}.previous");
+
+    let cached = cache::load(&path);
+    let interval = Endpoint::Leaderboard.min_interval();
+    let wait = http::remaining_interval(Endpoint::Leaderboard, SystemTime::now());
+    let is_recent = |entry: &Entry| {
+        SystemTime::now()
+            .duration_since(entry.fetched_at)
+            .unwrap_or_default()
+            < interval
+    };
+
+    let entry = match cached {
+        Some(entry) if is_recent(&entry) || !wait.is_zero() => {
+            println!(
+                "Showing the leaderboard fetched {} ago, leaderboards are fetched at most every {} minutes.",
+                entry.age(),
+                interval.as_secs() / 60
+            );
+            entry
+        }
+        None if !wait.is_zero() => fail(format!(
+            "A leaderboard was fetched less than {} minutes ago, try again in {}.",
+            interval.as_secs() / 60,
+            cache::format_age(wait)
+        )),
+        cached => {
+            let entry = cache::fetch(Endpoint::Leaderboard, &path, true)
+                .unwrap_or_else(|e| fail(format!("Failed to fetch the leaderboard: {e}")));
+
+            if let Some(old) = cached.filter(|old| old.body != entry.body) {
+                if let Err(e) = cache::store(&previous_path, &old.body, old.fetched_at) {
+                    eprintln!("Failed to keep the previous leaderboard: {e}");
+                }
+            }
+            entry
+        }
+    };
+
+    let previous = cache::load(&previous_path).and_then(|entry| {
+        println!(
+            "Changes since the leaderboard fetched {} ago are shown in green.",
+            entry.age()
+        );
+        leaderboard::parse(&entry.body).ok()
+    });
+
+    (parse(&entry.body), previous)
+}
+
+fn unlock_secs(day: Day, year: u16) -> u64 {
+    day.unlock_time(year)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn handle(source: &Source, year: Option<u16>, day: Option<Day>) {
+    let year = year.or_else(aoc_cli::get_year).unwrap_or_else(current_year);
+
+    let (leaderboard, previous) = match source {
+        Source::Fetch(id) => fetch(*id, year),
+        Source::File(path) => {
+            let json = fs::read_to_string(path)
+                .unwrap_or_else(|e| fail(format!("Failed to read \"{}\": {e}", path.display())));
+            (parse(&json), None)
+        }
+    };
+
+    let owner = leaderboard
+        .members
+        .iter()
+        .find(|member| member.id == leaderboard.owner_id)
+        .map_or_else(
+            || format!("#{}", leaderboard.owner_id),
+            |owner| owner.display_name(),
+        );
+    println!();
+    println!(
+        "{ANSI_BOLD}Private leaderboard of {owner}, {}{ANSI_RESET}",
+        leaderboard.year
+    );
+    println!();
+
+    let lines = match day {
+        Some(day) => {
+            println!("Day {day}, time since the puzzle unlocked:");
+            leaderboard::render_day(
+                &leaderboard,
+                day.into_inner(),
+                unlock_secs(day, leaderboard.year),
+            )
+        }
+        None => {
+            let now = SystemTime::now();
+            let days = all_days_in(leaderboard.year).count();
+            let unlocked = all_days_in(leaderboard.year)
+                .filter(|day| day.unlock_time(leaderboard.year) <= now)
+                .count();
+            leaderboard::render_matrix(
+                &leaderboard,
+                previous.as_ref(),
+                u8::try_from(days).unwrap(),
+                u8::try_from(unlocked).unwrap(),
+            )
+        }
+    };
+
+    for line in lines {
+        println!("{line}");
+    }
+}
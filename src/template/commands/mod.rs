@@ -1,5 +1,14 @@
 pub mod all;
+pub mod auth;
+pub mod completions;
+pub mod crosscheck;
+pub mod decrypt;
 pub mod download;
+pub mod encrypt;
+pub mod leaderboard;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod view;
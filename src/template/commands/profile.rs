@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::flamegraph::{collapse_perf, render_svg, to_folded_lines};
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::Day;
+
+#[must_use]
+pub fn get_path_for_profile(day: Day, part: u8, extension: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("profiles")
+        .join(format!("{day}-{part}.{extension}"))
+}
+
+fn check_perf() -> bool {
+    Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Builds the solution with the `profiling` profile, which adds debug info to the release profile.
+fn build(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["build", "--quiet", "--profile", "profiling"])
+        .args(["--bin", &day.to_string()])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn get_path_for_exe(day: Day) -> PathBuf {
+    let target = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    Path::new(&target).join("profiling").join(day.to_string())
+}
+
+/// Samples one part, run repeatedly for `duration`, with `perf` and returns the output of `perf script`.
+fn record(
+    day: Day,
+    part: u8,
+    frequency: u32,
+    duration: Duration,
+    data: &Path,
+) -> Result<String, String> {
+    let status = Command::new("perf")
+        .args(["record", "--quiet", "-F", &frequency.to_string()])
+        .args(["--call-graph", "dwarf", "-o"])
+        .arg(data)
+        .arg("--")
+        .arg(get_path_for_exe(day))
+        .args(["--part", &part.to_string()])
+        .args(["--duration", &duration.as_secs_f64().to_string()])
+        .status()
+        .map_err(|e| e.to_string())?;
+
+    if !status.success() {
+        return Err(format!("perf record exited with {status}"));
+    }
+
+    let output = Command::new("perf")
+        .args(["script", "-i"])
+        .arg(data)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| e.to_string())?;
+
+    if !output.status.success() {
+        return Err(format!("perf script exited with {}", output.status));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn profile(day: Day, part: u8, frequency: u32, duration: Duration) -> Result<PathBuf, String> {
+    let data = get_path_for_profile(day, part, "perf.data");
+    if let Some(parent) = data.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let script = record(day, part, frequency, duration, &data);
+    let _ = fs::remove_file(&data);
+
+    let folded = collapse_perf(&script?);
+    if folded.is_empty() {
+        return Err("no samples were recorded".into());
+    }
+
+    let svg = get_path_for_profile(day, part, "svg");
+    fs::write(
+        get_path_for_profile(day, part, "folded"),
+        to_folded_lines(&folded),
+    )
+    .map_err(|e| e.to_string())?;
+    fs::write(
+        &svg,
+        render_svg(&folded, &format!("Day {day}, part {part}")),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(svg)
+}
+
+pub fn handle(day: Day, part: Option<u8>, frequency: u32, duration: Duration) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Day {day} has not been scaffolded yet.");
+        process::exit(1);
+    }
+
+    if part.is_some_and(|part| !(1..=2).contains(&part)) {
+        eprintln!("--part must be 1 or 2.");
+        process::exit(1);
+    }
+
+    if !check_perf() {
+        eprintln!("command \"perf\" not found or not callable. Profiling requires perf, which is available on Linux (e.g. in the \"linux-tools\" package).");
+        process::exit(1);
+    }
+
+    if !build(day) {
+        eprintln!("Failed to build day {day}.");
+        process::exit(1);
+    }
+
+    let parts = part.map_or_else(|| vec![1, 2], |part| vec![part]);
+    let mut failed = false;
+
+    for part in parts {
+        match profile(day, part, frequency, duration) {
+            Ok(path) => println!("Wrote flame graph to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to profile part {part}: {e}");
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
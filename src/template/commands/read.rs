@@ -1,16 +1,36 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::{puzzle, render};
+use crate::{configured_year, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(day: Day, part: Option<u8>) {
+    let year = configured_year();
+    let path = puzzle::get_path(year, day);
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let Ok(markdown) = fs::read_to_string(&path) else {
+        eprintln!(
+            "No puzzle description found at \"{}\". Run \"cargo download {day}\" to download it.",
+            path.display()
+        );
         process::exit(1);
     };
+
+    let description = match part {
+        Some(part) => puzzle::get_part(&markdown, part).unwrap_or_else(|| {
+            eprintln!(
+                "Part {part} of day {day} is not in \"{}\" yet. Solve part one, or run \"cargo download {day} --refresh\" if you already did.",
+                path.display()
+            );
+            process::exit(1);
+        }),
+        None => &markdown,
+    };
+
+    let previous = fs::read_to_string(puzzle::get_path_for_previous(year, day)).ok();
+    let text = render::render(description, previous.as_deref(), render::terminal_width());
+
+    if let Err(e) = render::page(&text) {
+        eprintln!("Failed to show the puzzle description: {e}");
+        process::exit(1);
+    }
 }
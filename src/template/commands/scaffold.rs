@@ -1,10 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::input;
+use crate::{configured_year, Day, DaySet};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -39,11 +41,38 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
+pub fn handle(days: DaySet) {
+    if days.is_empty() {
+        eprintln!("No days selected.");
+        process::exit(1);
+    }
+
+    let mut failed = false;
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        failed |= !scaffold(day);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Creates the files for a day, returns whether it succeeded.
+fn scaffold(day: Day) -> bool {
+    let input_path = input::get_path_for_puzzle(configured_year(), day)
+        .display()
+        .to_string();
     let example_path = format!("data/examples/{day}.txt");
     let module_path = format!("src/bin/{day}.rs");
 
@@ -51,7 +80,7 @@ pub fn handle(day: Day) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+            return false;
         }
     };
 
@@ -65,17 +94,19 @@ pub fn handle(day: Day) {
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
-    match create_file(&input_path) {
+    let created = fs::create_dir_all(Path::new(&input_path).parent().unwrap())
+        .and_then(|()| create_file(&input_path));
+    match created {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
@@ -85,10 +116,11 @@ pub fn handle(day: Day) {
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    true
 }
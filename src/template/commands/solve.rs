@@ -1,14 +1,113 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::budget::Budget;
+use crate::template::heap::HEAP_FEATURE;
+use crate::template::input::Input;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{Day, DaySet};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+/// Which implementation of the parts to run, see the `variants` argument of `solution!`.
+pub enum Variant {
+    Default,
+    Named(String),
+    All,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: DaySet,
+    release: bool,
+    time: bool,
+    trace: bool,
+    submit_part: Option<u8>,
+    budget: Budget,
+    heap: bool,
+    variant: &Variant,
+    input: &Input,
+) {
+    if days.is_empty() {
+        eprintln!("No days selected.");
+        process::exit(1);
+    }
+
+    if submit_part.is_some() && days.len() > 1 {
+        eprintln!("--submit can only be used when solving a single day.");
+        process::exit(1);
+    }
+
+    if matches!(input, Input::File(_) | Input::Stdin) && days.len() > 1 {
+        eprintln!("--input can only be used when solving a single day.");
+        process::exit(1);
+    }
+
+    if let Some(day) = days.single() {
+        let success = solve(
+            day,
+            release,
+            time,
+            trace,
+            submit_part,
+            budget,
+            heap,
+            variant,
+            input,
+        );
+        if !success {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut failed = false;
+
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+        failed |= !solve(
+            day,
+            release,
+            time,
+            trace,
+            submit_part,
+            budget,
+            heap,
+            variant,
+            input,
+        );
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Runs the solution of a day and returns whether it succeeded.
+#[allow(clippy::too_many_arguments)]
+fn solve(
+    day: Day,
+    release: bool,
+    time: bool,
+    trace: bool,
+    submit_part: Option<u8>,
+    budget: Budget,
+    heap: bool,
+    variant: &Variant,
+    input: &Input,
+) -> bool {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    if heap {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(HEAP_FEATURE.to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -20,6 +119,22 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if trace {
+        cmd_args.push("--trace".to_string());
+    }
+
+    cmd_args.extend(budget.to_args());
+    cmd_args.extend(input.to_args());
+
+    match variant {
+        Variant::Default => {}
+        Variant::Named(name) => {
+            cmd_args.push("--variant".to_string());
+            cmd_args.push(name.clone());
+        }
+        Variant::All => cmd_args.push("--all-variants".to_string()),
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -27,5 +142,5 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap().success()
 }
@@ -0,0 +1,108 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::template::progress::{self, DayProgress};
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::{encryption, input, readme_stars, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, configured_year, Day, DaySet};
+
+struct DayStatus {
+    day: Day,
+    scaffolded: bool,
+    has_input: bool,
+    /// [`None`] if tests were not run.
+    tests_pass: Option<bool>,
+    progress: DayProgress,
+}
+
+fn has_content(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn run_tests(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn collect_status(day: Day, with_tests: bool) -> DayStatus {
+    let scaffolded = Path::new(&get_path_for_bin(day)).exists();
+    let input = input::get_path_for_puzzle(configured_year(), day);
+
+    DayStatus {
+        day,
+        scaffolded,
+        has_input: has_content(&input) || has_content(&encryption::get_path_for_encrypted(&input)),
+        tests_pass: (with_tests && scaffolded).then(|| run_tests(day)),
+        progress: progress::load(day),
+    }
+}
+
+fn check(value: bool) -> &'static str {
+    if value {
+        "✓"
+    } else {
+        "✗"
+    }
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or_else(|| "-".into(), |t| format!("{t:.1?}"))
+}
+
+fn print_row(status: &DayStatus) {
+    let star = |part: u8| {
+        if status.progress.part(part).answer.is_some() {
+            "★"
+        } else {
+            "·"
+        }
+    };
+
+    let tests = match status.tests_pass {
+        Some(pass) => check(pass),
+        None if status.scaffolded => "?",
+        None => "-",
+    };
+
+    println!(
+        "{:<5}{:<6}{:<7}{:<9}{:<8}{:<8}{}",
+        status.day.to_string(),
+        check(status.scaffolded),
+        check(status.has_input),
+        tests,
+        star(1),
+        star(2),
+        format_time(status.progress.best_total_time()),
+    );
+}
+
+pub fn handle(days: DaySet, with_tests: bool) {
+    println!("{ANSI_BOLD}Day  Code  Input  Example  Part 1  Part 2  Fastest{ANSI_RESET}");
+
+    let statuses: Vec<DayStatus> = days
+        .iter()
+        .map(|day| {
+            let status = collect_status(day, with_tests);
+            print_row(&status);
+            status
+        })
+        .collect();
+
+    let stars: usize = statuses.iter().map(|s| s.progress.stars()).sum();
+    println!("---");
+    println!("{ANSI_BOLD}Stars:{ANSI_RESET} {stars}/{}", days.len() * 2);
+
+    // the README table lists every day, not only the selected ones.
+    let progress: Vec<_> = all_days().map(|day| (day, progress::load(day))).collect();
+
+    match readme_stars::update(configured_year(), &progress) {
+        Ok(()) => println!("Successfully updated README with stars."),
+        Err(_) => eprintln!("Failed to update readme with stars."),
+    }
+}
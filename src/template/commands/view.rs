@@ -0,0 +1,77 @@
+use std::io::{self, BufRead, Write};
+use std::{fs, process};
+
+use crate::template::trace::{get_path_for_trace, parse_frames, Frame};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+enum Step {
+    Next,
+    Previous,
+    Jump(usize),
+    Quit,
+}
+
+fn parse_step(input: &str) -> Option<Step> {
+    match input.trim() {
+        "" | "n" => Some(Step::Next),
+        "p" => Some(Step::Previous),
+        "q" => Some(Step::Quit),
+        x => x.parse().ok().map(Step::Jump),
+    }
+}
+
+fn print_frame(frame: &Frame, index: usize, total: usize) {
+    print!("{ANSI_CLEAR}");
+    println!(
+        "{ANSI_BOLD}Frame {}/{total}{ANSI_RESET}: {}",
+        index + 1,
+        frame.label
+    );
+    println!("---");
+    println!("{}", frame.body);
+    println!("---");
+    print!("{ANSI_ITALIC}[enter] next, [p] previous, [<n>] jump, [q] quit{ANSI_RESET} > ");
+    let _ = io::stdout().flush();
+}
+
+pub fn handle(day: Day) {
+    let path = get_path_for_trace(day);
+
+    let frames = match fs::read_to_string(&path) {
+        Ok(s) => parse_frames(&s),
+        Err(e) => {
+            eprintln!("Failed to read trace file {}: {e}", path.display());
+            eprintln!("Record one with `cargo solve {day} --trace`.");
+            process::exit(1);
+        }
+    };
+
+    if frames.is_empty() {
+        println!("No frames recorded for day {day}.");
+        return;
+    }
+
+    let mut index = 0;
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        print_frame(&frames[index], index, frames.len());
+
+        let Some(Ok(line)) = lines.next() else {
+            break;
+        };
+
+        match parse_step(&line) {
+            Some(Step::Next) => index = (index + 1).min(frames.len() - 1),
+            Some(Step::Previous) => index = index.saturating_sub(1),
+            Some(Step::Jump(n)) => index = n.clamp(1, frames.len()) - 1,
+            Some(Step::Quit) => break,
+            None => {}
+        }
+    }
+
+    println!();
+}
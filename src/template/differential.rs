@@ -0,0 +1,302 @@
+/// Differential testing harness: runs a fast implementation against a slow reference implementation
+/// on generated inputs and shrinks the first disagreement down to a minimal counterexample.
+use std::fmt::Debug;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, ops::Range};
+
+/// Number of generated cases per check, unless overridden by `AOC_DIFF_CASES`.
+const DEFAULT_CASES: u32 = 256;
+
+/// Upper bound on shrinking steps so that a bad shrinker can't loop forever.
+const MAX_SHRINK_STEPS: u32 = 10_000;
+
+/// A small, seedable pseudo-random number generator (`SplitMix64`).
+/// Good enough for generating puzzle inputs, not meant for anything else.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in the half-open `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot generate a value in an empty range"
+        );
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Picks a random element of `items`. Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.range(0..items.len() as u64) as usize;
+        &items[index]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Types that know how to produce "smaller" versions of a value.
+/// Candidates should be ordered from most to least aggressive.
+pub trait Shrink: Sized {
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                let mut delta = *self;
+                while delta > 0 {
+                    candidates.push(*self - delta);
+                    delta /= 2;
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+impl_shrink_unsigned!(u8, u16, u32, u64, usize);
+
+impl Shrink for char {
+    fn shrink(&self) -> Vec<Self> {
+        vec![]
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        // drop single elements first, then shrink elements in place.
+        for i in 0..self.len() {
+            let mut smaller = self.clone();
+            smaller.remove(i);
+            candidates.push(smaller);
+        }
+
+        for (i, item) in self.iter().enumerate() {
+            for shrunk in item.shrink() {
+                let mut smaller = self.clone();
+                smaller[i] = shrunk;
+                candidates.push(smaller);
+            }
+        }
+
+        candidates
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let first = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let second = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+        first.chain(second).collect()
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone, C: Shrink + Clone> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        let first = (self.0.shrink().into_iter()).map(|a| (a, self.1.clone(), self.2.clone()));
+        let second = (self.1.shrink().into_iter()).map(|b| (self.0.clone(), b, self.2.clone()));
+        let third = (self.2.shrink().into_iter()).map(|c| (self.0.clone(), self.1.clone(), c));
+        first.chain(second).chain(third).collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A pair of implementations that must agree, plus a generator for their input.
+///
+/// Implement this in a day's test module for a marker type, then call [`check`] from a test.
+pub trait Differential {
+    type Input: Clone + Debug + Shrink;
+    type Output: PartialEq + Debug;
+
+    /// Produces a random input.
+    fn generate(rng: &mut Rng) -> Self::Input;
+
+    /// Whether an input satisfies the preconditions of both implementations.
+    /// Shrunk candidates that are not valid are skipped.
+    fn is_valid(_input: &Self::Input) -> bool {
+        true
+    }
+
+    /// The implementation under test.
+    fn fast(input: Self::Input) -> Self::Output;
+
+    /// The slow, obviously correct implementation.
+    fn reference(input: Self::Input) -> Self::Output;
+}
+
+/// A disagreement between the fast and the reference implementation.
+#[derive(Debug, PartialEq)]
+pub struct Counterexample<I, O> {
+    pub seed: u64,
+    pub input: I,
+    pub fast: O,
+    pub reference: O,
+}
+
+/// Runs `cases` generated inputs through both implementations starting at `seed`.
+/// Returns the shrunk counterexample for the first disagreement, if any.
+pub fn run<D: Differential>(seed: u64, cases: u32) -> Option<Counterexample<D::Input, D::Output>> {
+    let mut rng = Rng::new(seed);
+
+    let failing = (0..cases)
+        .map(|_| D::generate(&mut rng))
+        .find(|input| !agrees::<D>(input))?;
+
+    let input = shrink::<D>(failing);
+    let fast = D::fast(input.clone());
+    let reference = D::reference(input.clone());
+
+    Some(Counterexample {
+        seed,
+        input,
+        fast,
+        reference,
+    })
+}
+
+/// Runs the differential check and panics with the minimal counterexample if one is found.
+///
+/// The amount of cases can be set with `AOC_DIFF_CASES`, and a failing run can be reproduced by
+/// setting `AOC_DIFF_SEED` to the seed printed in the panic message.
+pub fn check<D: Differential>() {
+    let seed = env_var("AOC_DIFF_SEED").unwrap_or_else(time_seed);
+    let cases = env_var("AOC_DIFF_CASES").map_or(DEFAULT_CASES, |cases| {
+        u32::try_from(cases).unwrap_or(u32::MAX)
+    });
+
+    if let Some(counterexample) = run::<D>(seed, cases) {
+        panic!(
+            "implementations disagree (AOC_DIFF_SEED={}):\n  input:     {:?}\n  fast:      {:?}\n  reference: {:?}",
+            counterexample.seed, counterexample.input, counterexample.fast, counterexample.reference
+        );
+    }
+}
+
+fn agrees<D: Differential>(input: &D::Input) -> bool {
+    D::fast(input.clone()) == D::reference(input.clone())
+}
+
+fn shrink<D: Differential>(mut input: D::Input) -> D::Input {
+    for _ in 0..MAX_SHRINK_STEPS {
+        let smaller = input
+            .shrink()
+            .into_iter()
+            .find(|candidate| D::is_valid(candidate) && !agrees::<D>(candidate));
+
+        match smaller {
+            Some(smaller) => input = smaller,
+            None => break,
+        }
+    }
+    input
+}
+
+fn env_var(key: &str) -> Option<u64> {
+    env::var(key).ok()?.parse().ok()
+}
+
+fn time_seed() -> u64 {
+    #[allow(clippy::cast_possible_truncation)]
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run, Differential, Rng, Shrink};
+
+    /// Agrees with the reference for every value below 100.
+    struct BrokenAbove100;
+
+    impl Differential for BrokenAbove100 {
+        type Input = Vec<u32>;
+        type Output = u32;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            (0..rng.range(0..10))
+                .map(|_| rng.range(0..1000) as u32)
+                .collect()
+        }
+
+        fn fast(input: Self::Input) -> Self::Output {
+            input.iter().map(|x| if *x > 100 { 0 } else { *x }).sum()
+        }
+
+        fn reference(input: Self::Input) -> Self::Output {
+            input.iter().sum()
+        }
+    }
+
+    struct Identical;
+
+    impl Differential for Identical {
+        type Input = (u64, u64);
+        type Output = u64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            (rng.range(0..1000), rng.range(0..1000))
+        }
+
+        fn fast(input: Self::Input) -> Self::Output {
+            input.0 * input.1
+        }
+
+        fn reference(input: Self::Input) -> Self::Output {
+            (0..input.0).map(|_| input.1).sum()
+        }
+    }
+
+    #[test]
+    fn rng_is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn shrinks_unsigned_towards_zero() {
+        assert_eq!(8_u32.shrink(), vec![0, 4, 6, 7]);
+        assert_eq!(0_u32.shrink(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn finds_no_counterexample_for_equal_implementations() {
+        assert_eq!(run::<Identical>(1, 200), None);
+    }
+
+    #[test]
+    fn shrinks_counterexample_to_minimum() {
+        let counterexample = run::<BrokenAbove100>(7, 500).unwrap();
+        assert_eq!(counterexample.input, vec![101]);
+        assert_eq!(counterexample.fast, 0);
+        assert_eq!(counterexample.reference, 101);
+    }
+}
@@ -0,0 +1,226 @@
+/// Encrypted puzzle inputs, which can be committed next to the solutions.
+///
+/// An input `{day}.txt` is stored as `{day}.txt.enc`, encrypted with ChaCha20-Poly1305. The key is derived with
+/// Argon2id from a passphrase, which is read from `AOC_INPUT_KEY`, from the file in `AOC_INPUT_KEY_FILE` or from
+/// `.aoc-key` in the project root. Reading an input falls back to its encrypted file, see [`read_to_string`].
+///
+/// File layout: [`MAGIC`], a 16 byte salt, a 12 byte nonce and the ciphertext.
+use std::ffi::OsString;
+use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
+use std::{env, error, fs, io};
+
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::template::{input, input_sets};
+use crate::{configured_year, DaySet};
+
+pub const EXTENSION: &str = "enc";
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE_ENV: &str = "AOC_INPUT_KEY_FILE";
+pub const DEFAULT_KEY_FILE: &str = ".aoc-key";
+
+const MAGIC: &[u8; 8] = b"aoc-enc1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Neither `AOC_INPUT_KEY` nor a key file is set.
+    MissingKey,
+    /// The key file could not be read.
+    KeyFile(PathBuf, io::Error),
+    /// The file is not an encrypted input.
+    Format,
+    /// The key is wrong or the file was modified.
+    Decrypt,
+    Io(io::Error),
+}
+
+impl error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no key found, set {KEY_ENV} to the passphrase or put it in {DEFAULT_KEY_FILE}"
+            ),
+            Error::KeyFile(path, e) => {
+                write!(f, "could not read key file \"{}\": {e}", path.display())
+            }
+            Error::Format => f.write_str("not an encrypted input"),
+            Error::Decrypt => {
+                f.write_str("decryption failed, the key is wrong or the file is corrupted")
+            }
+            Error::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// A passphrase, from which a key is derived for every file.
+pub struct Passphrase(Vec<u8>);
+
+impl Passphrase {
+    /// Reads the passphrase from the environment or the key file.
+    pub fn load() -> Result<Self, Error> {
+        if let Some(passphrase) = env::var_os(KEY_ENV).filter(|s| !s.is_empty()) {
+            return Ok(Self::from_os_string(passphrase));
+        }
+
+        let (path, explicit) = match env::var_os(KEY_FILE_ENV) {
+            Some(path) => (PathBuf::from(path), true),
+            None => (env::current_dir()?.join(DEFAULT_KEY_FILE), false),
+        };
+
+        match fs::read(&path) {
+            Ok(contents) => Ok(Self::new(contents.trim_ascii())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !explicit => Err(Error::MissingKey),
+            Err(e) => Err(Error::KeyFile(path, e)),
+        }
+    }
+
+    #[must_use]
+    pub fn new(passphrase: &[u8]) -> Self {
+        Self(passphrase.to_vec())
+    }
+
+    fn from_os_string(s: OsString) -> Self {
+        Self(s.to_string_lossy().as_bytes().to_vec())
+    }
+
+    fn cipher(&self, salt: &[u8]) -> Result<ChaCha20Poly1305, Error> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(&self.0, salt, &mut key)
+            .map_err(|_| Error::Decrypt)?;
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+}
+
+pub fn encrypt(plaintext: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, Error> {
+    let mut salt = [0_u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = passphrase
+        .cipher(&salt)?
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| Error::Format)?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+pub fn decrypt(data: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, Error> {
+    let data = data.strip_prefix(MAGIC).ok_or(Error::Format)?;
+    if data.len() < SALT_LEN + NONCE_LEN {
+        return Err(Error::Format);
+    }
+
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    passphrase
+        .cipher(salt)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::Decrypt)
+}
+
+/// The encrypted file of an input, e.g. `data/2023/inputs/01.txt.enc`.
+#[must_use]
+pub fn get_path_for_encrypted(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Whether an input exists, either as plain text or encrypted.
+#[must_use]
+pub fn exists(path: &Path) -> bool {
+    path.exists() || get_path_for_encrypted(path).exists()
+}
+
+/// Reads a text file, or decrypts its encrypted file if only that exists.
+pub fn read_to_string(path: &Path) -> Result<String, Error> {
+    let encrypted = get_path_for_encrypted(path);
+    if path.exists() || !encrypted.exists() {
+        return Ok(fs::read_to_string(path)?);
+    }
+
+    let plaintext = decrypt(&fs::read(&encrypted)?, &Passphrase::load()?)?;
+    String::from_utf8(plaintext).map_err(|_| Error::Format)
+}
+
+/// The inputs of `days`, of the account and of every input set. Inputs may not exist.
+#[must_use]
+pub fn get_paths_for_inputs(days: &DaySet) -> Vec<PathBuf> {
+    let year = configured_year();
+    let sets = input_sets::list();
+
+    days.iter()
+        .flat_map(|day| {
+            let own = input::get_path_for_puzzle(year, day);
+            let others = sets
+                .iter()
+                .map(move |name| input_sets::get_path_for_input(name, day));
+            std::iter::once(own).chain(others)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, get_path_for_encrypted, Error, Passphrase};
+    use std::path::Path;
+
+    #[test]
+    fn round_trips() {
+        let passphrase = Passphrase::new(b"correct horse battery staple");
+        let encrypted = encrypt(b"1abc2\npqr3stu8vwx", &passphrase).unwrap();
+
+        assert!(encrypted.starts_with(b"aoc-enc1"));
+        assert_eq!(
+            decrypt(&encrypted, &passphrase).unwrap(),
+            b"1abc2\npqr3stu8vwx"
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_key_and_plain_text() {
+        let encrypted = encrypt(b"input", &Passphrase::new(b"one")).unwrap();
+
+        assert!(matches!(
+            decrypt(&encrypted, &Passphrase::new(b"two")),
+            Err(Error::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt(b"input", &Passphrase::new(b"one")),
+            Err(Error::Format)
+        ));
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            get_path_for_encrypted(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
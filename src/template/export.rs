@@ -0,0 +1,430 @@
+/// Writes benchmark results of `all --time --export <file>` to JSON or CSV, for aggregating results of several machines.
+///
+/// JSON (`version` 1):
+///
+/// ```text
+/// {
+///   "version": 1,
+///   "generated_at": <unix seconds>,
+///   "machine": "...", "rustc": "...", "commit": "...",
+///   "total_nanos": <f64>,
+///   "days": [{
+///     "day": 1,
+///     "total_nanos": <f64>,
+///     "parse": <stats> | null,
+///     "parts": [{ "part": 1, "status": "ok", ...<stats> }, ...]
+///   }]
+/// }
+/// ```
+///
+/// where `<stats>` are `mean_nanos`, `stddev_nanos`, `min_nanos`, `median_nanos`, `max_nanos` (null without raw samples),
+/// `samples` and `raw_nanos` (every sample, in order). The `status` of a part is one of `ok`, `timed_out`, `out_of_memory`
+/// or `unsolved`, the statistics are null unless it is `ok`. Parts also have a `heap` object with `peak_bytes`,
+/// `allocations` and `allocated_bytes`, which is null unless solutions ran with `--heap`.
+///
+/// CSV has one row per raw sample with the columns in [`CSV_HEADER`]. The statistics are repeated for every sample of
+/// a part, parts without samples have a single row with empty sample columns. Parsing is exported as part `parse`.
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, fs, io};
+
+use crate::template::budget::{OUT_OF_MEMORY, TIMED_OUT};
+use crate::template::heap::HeapStats;
+use crate::template::readme_benchmarks::{Metadata, Stats, Timings};
+
+pub const CSV_HEADER: &str = "machine,rustc,commit,day,part,status,mean_nanos,stddev_nanos,min_nanos,median_nanos,max_nanos,samples,peak_bytes,allocations,allocated_bytes,sample_index,sample_nanos";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// Where to export results to. The format is inferred from the file extension.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    pub path: PathBuf,
+    pub format: ExportFormat,
+}
+
+#[derive(Debug)]
+pub struct UnknownExportFormatError(String);
+
+impl fmt::Display for UnknownExportFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot export to \"{}\", expected a .json or .csv file",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownExportFormatError {}
+
+/// Parses the `--export` argument, e.g. `results.json`.
+pub fn parse_export(s: &str) -> Result<Export, UnknownExportFormatError> {
+    let path = PathBuf::from(s);
+    let format = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => ExportFormat::Json,
+        Some("csv") => ExportFormat::Csv,
+        _ => return Err(UnknownExportFormatError(s.into())),
+    };
+    Ok(Export { path, format })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Ok,
+    TimedOut,
+    OutOfMemory,
+    Unsolved,
+}
+
+impl Status {
+    fn of(part: Option<&String>, stats: Option<&Stats>) -> Self {
+        match (part.map(String::as_str), stats) {
+            (_, Some(_)) => Status::Ok,
+            (Some(TIMED_OUT), None) => Status::TimedOut,
+            (Some(OUT_OF_MEMORY), None) => Status::OutOfMemory,
+            (_, None) => Status::Unsolved,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::TimedOut => "timed_out",
+            Status::OutOfMemory => "out_of_memory",
+            Status::Unsolved => "unsolved",
+        }
+    }
+}
+
+/// Mean and standard deviation, exact if raw samples are present. Printed timings are rounded.
+#[allow(clippy::cast_precision_loss)]
+fn moments(stats: &Stats) -> (f64, f64) {
+    if stats.raw_nanos.is_empty() {
+        return (stats.nanos, stats.stddev_nanos);
+    }
+
+    let len = stats.raw_nanos.len() as f64;
+    let mean = stats.raw_nanos.iter().map(|&x| x as f64).sum::<f64>() / len;
+    let variance = stats
+        .raw_nanos
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / len;
+    (mean, variance.sqrt())
+}
+
+/// Minimum, median and maximum of the raw samples.
+fn spread(raw: &[u64]) -> Option<(u64, u64, u64)> {
+    let mut sorted = raw.to_vec();
+    sorted.sort_unstable();
+    Some((*sorted.first()?, sorted[sorted.len() / 2], *sorted.last()?))
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "null".into(), |v| v.to_string())
+}
+
+fn json_stats(stats: Option<&Stats>) -> String {
+    let spread = stats.and_then(|s| spread(&s.raw_nanos));
+    let raw = stats.map(|s| {
+        let raw: Vec<_> = s.raw_nanos.iter().map(u64::to_string).collect();
+        format!("[{}]", raw.join(","))
+    });
+
+    format!(
+        "\"mean_nanos\": {}, \"stddev_nanos\": {}, \"min_nanos\": {}, \"median_nanos\": {}, \"max_nanos\": {}, \"samples\": {}, \"raw_nanos\": {}",
+        json_option(stats.map(|s| moments(s).0)),
+        json_option(stats.map(|s| moments(s).1)),
+        json_option(spread.map(|s| s.0)),
+        json_option(spread.map(|s| s.1)),
+        json_option(spread.map(|s| s.2)),
+        json_option(stats.map(|s| s.samples)),
+        json_option(raw),
+    )
+}
+
+fn json_heap(heap: Option<&HeapStats>) -> String {
+    heap.map_or_else(
+        || "null".into(),
+        |h| {
+            format!(
+                "{{ \"peak_bytes\": {}, \"allocations\": {}, \"allocated_bytes\": {} }}",
+                h.peak_bytes, h.allocations, h.allocated_bytes
+            )
+        },
+    )
+}
+
+fn to_json(
+    timings: &[Timings],
+    total_nanos: f64,
+    metadata: &Metadata,
+    generated_at: u64,
+) -> String {
+    let days: Vec<String> = timings
+        .iter()
+        .map(|timing| {
+            let parts: Vec<String> = [&timing.part_1, &timing.part_2]
+                .iter()
+                .zip(&timing.stats)
+                .enumerate()
+                .map(|(i, (part, stats))| {
+                    format!(
+                        "        {{ \"part\": {}, \"status\": \"{}\", {}, \"heap\": {} }}",
+                        i + 1,
+                        Status::of(part.as_ref(), stats.as_ref()).as_str(),
+                        json_stats(stats.as_ref()),
+                        json_heap(timing.heap[i].as_ref())
+                    )
+                })
+                .collect();
+
+            let parse = timing.parse.as_ref().map_or_else(
+                || "null".into(),
+                |s| format!("{{ {} }}", json_stats(Some(s))),
+            );
+
+            [
+                "    {".into(),
+                format!("      \"day\": {},", timing.day.into_inner()),
+                format!("      \"total_nanos\": {},", timing.total_nanos),
+                format!("      \"parse\": {parse},"),
+                "      \"parts\": [".into(),
+                parts.join(",\n"),
+                "      ]".into(),
+                "    }".into(),
+            ]
+            .join("\n")
+        })
+        .collect();
+
+    [
+        "{".into(),
+        "  \"version\": 1,".into(),
+        format!("  \"generated_at\": {generated_at},"),
+        format!("  \"machine\": {},", json_string(&metadata.machine)),
+        format!("  \"rustc\": {},", json_string(&metadata.rustc)),
+        format!("  \"commit\": {},", json_string(&metadata.commit)),
+        format!("  \"total_nanos\": {total_nanos},"),
+        "  \"days\": [".into(),
+        days.join(",\n"),
+        "  ]".into(),
+        "}".into(),
+        String::new(),
+    ]
+    .join("\n")
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.into()
+    }
+}
+
+fn to_csv(timings: &[Timings], metadata: &Metadata) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+
+    let prefix = [&metadata.machine, &metadata.rustc, &metadata.commit]
+        .map(|s| csv_field(s))
+        .join(",");
+
+    for timing in timings {
+        let parse = timing
+            .parse
+            .as_ref()
+            .map(|s| ("parse".into(), Status::Ok, Some(s), None));
+        let parts = [&timing.part_1, &timing.part_2]
+            .into_iter()
+            .zip(&timing.stats)
+            .enumerate()
+            .map(|(i, (part, stats))| {
+                let status = Status::of(part.as_ref(), stats.as_ref());
+                ((i + 1).to_string(), status, stats.as_ref(), timing.heap[i])
+            });
+
+        for (part, status, stats, heap) in parse.into_iter().chain(parts) {
+            let spread = stats.and_then(|s| spread(&s.raw_nanos));
+            let columns = [
+                stats.map(|s| moments(s).0.to_string()),
+                stats.map(|s| moments(s).1.to_string()),
+                spread.map(|s| s.0.to_string()),
+                spread.map(|s| s.1.to_string()),
+                spread.map(|s| s.2.to_string()),
+                stats.map(|s| s.samples.to_string()),
+                heap.map(|h| h.peak_bytes.to_string()),
+                heap.map(|h| h.allocations.to_string()),
+                heap.map(|h| h.allocated_bytes.to_string()),
+            ]
+            .map(Option::unwrap_or_default)
+            .join(",");
+
+            let row = format!(
+                "{prefix},{},{part},{},{columns}",
+                timing.day.into_inner(),
+                status.as_str()
+            );
+
+            match stats.map(|s| &s.raw_nanos).filter(|raw| !raw.is_empty()) {
+                Some(raw) => {
+                    for (index, nanos) in raw.iter().enumerate() {
+                        lines.push(format!("{row},{index},{nanos}"));
+                    }
+                }
+                None => lines.push(format!("{row},,")),
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Writes the results of a timed run to `export.path`.
+pub fn write(
+    export: &Export,
+    timings: &[Timings],
+    total_nanos: f64,
+    metadata: &Metadata,
+) -> Result<(), io::Error> {
+    let contents = match export.format {
+        ExportFormat::Json => {
+            let generated_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            to_json(timings, total_nanos, metadata, generated_at)
+        }
+        ExportFormat::Csv => to_csv(timings, metadata),
+    };
+
+    if let Some(parent) = export.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(Path::new(&export.path), contents)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_export, to_csv, to_json, ExportFormat, CSV_HEADER};
+    use crate::day;
+    use crate::template::heap::HeapStats;
+    use crate::template::readme_benchmarks::{Metadata, Stats, Timings};
+
+    fn get_mock_metadata() -> Metadata {
+        Metadata {
+            machine: "CPU, 8 cores".into(),
+            rustc: "rustc 1.0.0".into(),
+            commit: "abc1234".into(),
+        }
+    }
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![Timings {
+            day: day!(1),
+            part_1: Some("2.0µs".into()),
+            part_2: Some("timed out".into()),
+            parse: None,
+            stats: [
+                Some(Stats {
+                    time: "2.0µs".into(),
+                    nanos: 2000.0,
+                    stddev_nanos: 800.0,
+                    samples: 3,
+                    raw_nanos: vec![3000, 1000, 2000],
+                }),
+                None,
+            ],
+            heap: [
+                Some(HeapStats {
+                    peak_bytes: 2048,
+                    allocations: 3,
+                    allocated_bytes: 4096,
+                }),
+                None,
+            ],
+            total_nanos: 2000.0,
+        }]
+    }
+
+    #[test]
+    fn infers_format_from_extension() {
+        assert_eq!(
+            parse_export("out/results.json").unwrap().format,
+            ExportFormat::Json
+        );
+        assert_eq!(
+            parse_export("results.csv").unwrap().format,
+            ExportFormat::Csv
+        );
+        assert!(parse_export("results.txt").is_err());
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = to_json(&get_mock_timings(), 2000.0, &get_mock_metadata(), 42);
+        let expected = [
+            "{",
+            "  \"version\": 1,",
+            "  \"generated_at\": 42,",
+            "  \"machine\": \"CPU, 8 cores\",",
+            "  \"rustc\": \"rustc 1.0.0\",",
+            "  \"commit\": \"abc1234\",",
+            "  \"total_nanos\": 2000,",
+            "  \"days\": [",
+            "    {",
+            "      \"day\": 1,",
+            "      \"total_nanos\": 2000,",
+            "      \"parse\": null,",
+            "      \"parts\": [",
+            "        { \"part\": 1, \"status\": \"ok\", \"mean_nanos\": 2000, \"stddev_nanos\": 816.496580927726, \"min_nanos\": 1000, \"median_nanos\": 2000, \"max_nanos\": 3000, \"samples\": 3, \"raw_nanos\": [3000,1000,2000], \"heap\": { \"peak_bytes\": 2048, \"allocations\": 3, \"allocated_bytes\": 4096 } },",
+            "        { \"part\": 2, \"status\": \"timed_out\", \"mean_nanos\": null, \"stddev_nanos\": null, \"min_nanos\": null, \"median_nanos\": null, \"max_nanos\": null, \"samples\": null, \"raw_nanos\": null, \"heap\": null }",
+            "      ]",
+            "    }",
+            "  ]",
+            "}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = to_csv(&get_mock_timings(), &get_mock_metadata());
+        let expected = [
+            CSV_HEADER,
+            "\"CPU, 8 cores\",rustc 1.0.0,abc1234,1,1,ok,2000,816.496580927726,1000,2000,3000,3,2048,3,4096,0,3000",
+            "\"CPU, 8 cores\",rustc 1.0.0,abc1234,1,1,ok,2000,816.496580927726,1000,2000,3000,3,2048,3,4096,1,1000",
+            "\"CPU, 8 cores\",rustc 1.0.0,abc1234,1,1,ok,2000,816.496580927726,1000,2000,3000,3,2048,3,4096,2,2000",
+            "\"CPU, 8 cores\",rustc 1.0.0,abc1234,1,2,timed_out,,,,,,,,,,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+}
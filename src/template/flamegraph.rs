@@ -0,0 +1,279 @@
+/// Turns `perf script` output into folded stacks and renders them as a flame graph.
+///
+/// Folded stacks have one line per unique stack, frames from the root to the leaf separated by `;`, followed by the
+/// number of samples, e.g. `main;_05::part_two;_05::get_location 42`. This is the format other flame graph tools read.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Sample counts per stack. Frames are ordered from the root to the leaf.
+pub type Folded = BTreeMap<Vec<String>, usize>;
+
+/// Removes the address offset and the hash rust appends to symbols, e.g. `_05::main::h1a2b3c4d5e6f7a8b+0x12`.
+fn clean_symbol(symbol: &str) -> String {
+    let symbol = symbol.split("+0x").next().unwrap_or(symbol);
+
+    match symbol.rsplit_once("::h") {
+        Some((name, hash)) if hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()) => {
+            name.to_string()
+        }
+        _ => symbol.to_string(),
+    }
+}
+
+/// Parses the output of `perf script`. Every sample starts with an unindented header line, followed by one indented
+/// line per frame (`<address> <symbol> (<binary>)`, leaf first) and ends with an empty line.
+#[must_use]
+pub fn collapse_perf(script: &str) -> Folded {
+    let mut folded = Folded::new();
+    let mut stack: Vec<String> = vec![];
+
+    let mut flush = |stack: &mut Vec<String>| {
+        if !stack.is_empty() {
+            stack.reverse();
+            *folded.entry(std::mem::take(stack)).or_default() += 1;
+        }
+    };
+
+    for line in script.lines() {
+        if line.trim().is_empty() {
+            flush(&mut stack);
+            continue;
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            // header of the next sample.
+            flush(&mut stack);
+            continue;
+        }
+
+        let mut parts = line.trim().splitn(2, ' ');
+        let _address = parts.next();
+        let Some(rest) = parts.next() else {
+            continue;
+        };
+
+        // the binary is the last parenthesized part, symbols may contain parentheses themselves.
+        let symbol = match rest.rfind(" (") {
+            Some(index) => &rest[..index],
+            None => rest,
+        };
+
+        stack.push(clean_symbol(symbol.trim()));
+    }
+
+    flush(&mut stack);
+    folded
+}
+
+/// Formats folded stacks, one line per stack.
+#[must_use]
+pub fn to_folded_lines(folded: &Folded) -> String {
+    folded
+        .iter()
+        .map(|(stack, count)| format!("{} {count}\n", stack.join(";")))
+        .collect()
+}
+
+/// A frame of the flame graph, with its children merged by name.
+#[derive(Default)]
+struct Node {
+    samples: usize,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn from_folded(folded: &Folded) -> Self {
+        let mut root = Node::default();
+        for (stack, &count) in folded {
+            root.samples += count;
+            let mut node = &mut root;
+            for frame in stack {
+                node = node.children.entry(frame.clone()).or_default();
+                node.samples += count;
+            }
+        }
+        root
+    }
+
+    fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|c| c.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+const WIDTH: f64 = 1200.0;
+const FRAME_HEIGHT: f64 = 16.0;
+const TOP: f64 = 40.0;
+const FONT_WIDTH: f64 = 7.0;
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A warm color that is stable for a frame name.
+fn color(name: &str) -> String {
+    let hash = name
+        .bytes()
+        .fold(5381_u32, |h, b| h.wrapping_mul(33) ^ u32::from(b));
+    let r = 205 + hash % 50;
+    let g = (hash / 50) % 180;
+    let b = (hash / 9000) % 55;
+    format!("rgb({r},{g},{b})")
+}
+
+struct Renderer {
+    out: String,
+    /// The y coordinate of the bottom of the graph.
+    bottom: f64,
+    /// Pixels per sample.
+    scale: f64,
+    total: usize,
+}
+
+impl Renderer {
+    #[allow(clippy::cast_precision_loss)]
+    fn render(&mut self, name: &str, node: &Node, x: f64, depth: usize) {
+        let width = node.samples as f64 * self.scale;
+        if width < 0.1 {
+            return;
+        }
+
+        // the root is drawn at the bottom, like in other flame graphs.
+        let y = self.bottom - FRAME_HEIGHT * (depth as f64 + 1.0);
+        let percent = node.samples as f64 * 100.0 / self.total as f64;
+        let title = format!("{} ({} samples, {percent:.2}%)", escape(name), node.samples);
+
+        let _ = write!(
+            self.out,
+            "  <g><title>{title}</title><rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{width:.1}\" height=\"{:.1}\" fill=\"{}\" rx=\"2\"/>",
+            FRAME_HEIGHT - 1.0,
+            color(name)
+        );
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let max_chars = ((width - 6.0) / FONT_WIDTH).max(0.0) as usize;
+        if max_chars >= 3 {
+            let label: String = if name.chars().count() > max_chars {
+                let short: String = name.chars().take(max_chars - 2).collect();
+                format!("{short}..")
+            } else {
+                name.to_string()
+            };
+            let _ = write!(
+                self.out,
+                "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                x + 3.0,
+                y + FRAME_HEIGHT - 4.0,
+                escape(&label)
+            );
+        }
+        self.out.push_str("</g>\n");
+
+        let mut child_x = x;
+        for (child_name, child) in &node.children {
+            self.render(child_name, child, child_x, depth + 1);
+            child_x += child.samples as f64 * self.scale;
+        }
+    }
+}
+
+/// Renders a standalone SVG flame graph. Frame widths are proportional to their samples.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn render_svg(folded: &Folded, title: &str) -> String {
+    let root = Node::from_folded(folded);
+    let height = TOP + FRAME_HEIGHT * (root.depth() as f64 + 1.0) + 10.0;
+    let scale = if root.samples > 0 {
+        (WIDTH - 20.0) / root.samples as f64
+    } else {
+        0.0
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" font-family=\"monospace\" font-size=\"11\">"
+    );
+    out.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"#fdf6e3\"/>\n");
+    let _ = writeln!(
+        out,
+        "  <text x=\"{}\" y=\"24\" font-size=\"16\" text-anchor=\"middle\">{} ({} samples)</text>",
+        WIDTH / 2.0,
+        escape(title),
+        root.samples
+    );
+
+    let mut renderer = Renderer {
+        out,
+        bottom: height - 10.0,
+        scale,
+        total: root.samples.max(1),
+    };
+    renderer.render("all", &root, 10.0, 0);
+
+    renderer.out.push_str("</svg>\n");
+    renderer.out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{clean_symbol, collapse_perf, render_svg, to_folded_lines};
+
+    const PERF_SCRIPT: &str = "05 1234 100.000001:     250000 cpu-clock:
+\t    55d1c3 _05::get_location::h0123456789abcdef+0x23 (/target/release/05)
+\t    55d1c4 _05::part_two+0x12 (/target/release/05)
+\t    55d1c5 main+0x8 (/target/release/05)
+
+05 1234 100.000002:     250000 cpu-clock:
+\t    55d1c3 _05::get_location::h0123456789abcdef+0x40 (/target/release/05)
+\t    55d1c4 _05::part_two+0x12 (/target/release/05)
+\t    55d1c5 main+0x8 (/target/release/05)
+
+05 1234 100.000003:     250000 cpu-clock:
+\t    7f0000 <alloc::vec::Vec<T> as core::clone::Clone>::clone+0x1 (/target/release/05)
+\t    55d1c4 _05::part_two+0x30 (/target/release/05)
+\t    55d1c5 main+0x8 (/target/release/05)
+";
+
+    #[test]
+    fn cleans_symbols() {
+        assert_eq!(
+            clean_symbol("_05::main::h0123456789abcdef+0x12"),
+            "_05::main"
+        );
+        assert_eq!(clean_symbol("[unknown]"), "[unknown]");
+        assert_eq!(clean_symbol("foo::hello"), "foo::hello");
+    }
+
+    #[test]
+    fn collapses_perf_script() {
+        let folded = collapse_perf(PERF_SCRIPT);
+        assert_eq!(
+            to_folded_lines(&folded),
+            [
+                "main;_05::part_two;<alloc::vec::Vec<T> as core::clone::Clone>::clone 1",
+                "main;_05::part_two;_05::get_location 2",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_svg() {
+        let svg = render_svg(&collapse_perf(PERF_SCRIPT), "Day 05, part 2");
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("Day 05, part 2 (3 samples)"));
+        assert!(svg.contains("<title>_05::get_location (2 samples, 66.67%)</title>"));
+        assert!(svg.contains("&lt;alloc::vec::Vec&lt;T&gt; as core::clone::Clone&gt;::clone"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,186 @@
+/// Heap usage of solutions, measured by a counting global allocator.
+///
+/// The allocator is only installed with the `count_allocations` feature, which `cargo solve 05 --heap` enables.
+/// Without it, [`measure`] does not report anything.
+use std::fmt;
+
+/// The cargo feature that installs the counting allocator. `solve --heap` and `all --heap` build solutions with it.
+pub const HEAP_FEATURE: &str = "count_allocations";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// The highest amount of memory that was allocated at once, on top of what was allocated before.
+    pub peak_bytes: usize,
+    /// The number of allocations, including reallocations.
+    pub allocations: usize,
+    /// The sum of all allocation sizes.
+    pub allocated_bytes: usize,
+}
+
+impl fmt::Display for HeapStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak {}, {} allocations, {} allocated",
+            format_bytes(self.peak_bytes),
+            self.allocations,
+            format_bytes(self.allocated_bytes)
+        )
+    }
+}
+
+/// Runs `func` and measures its heap usage, if the counting allocator is installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    #[cfg(feature = "count_allocations")]
+    {
+        let before = counting::snapshot();
+        counting::reset_peak();
+        let result = func();
+        let after = counting::snapshot();
+
+        let stats = HeapStats {
+            peak_bytes: after.peak.saturating_sub(before.current),
+            allocations: after.allocations - before.allocations,
+            allocated_bytes: after.allocated - before.allocated,
+        };
+
+        (result, Some(stats))
+    }
+
+    #[cfg(not(feature = "count_allocations"))]
+    {
+        (func(), None)
+    }
+}
+
+/// Formats bytes with binary units, e.g. `1.5 KiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "count_allocations")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Snapshot {
+        pub current: usize,
+        pub peak: usize,
+        pub allocations: usize,
+        pub allocated: usize,
+    }
+
+    pub fn snapshot() -> Snapshot {
+        Snapshot {
+            current: CURRENT.load(Ordering::SeqCst),
+            peak: PEAK.load(Ordering::SeqCst),
+            allocations: ALLOCATIONS.load(Ordering::SeqCst),
+            allocated: ALLOCATED.load(Ordering::SeqCst),
+        }
+    }
+
+    pub fn reset_peak() {
+        PEAK.store(CURRENT.load(Ordering::SeqCst), Ordering::SeqCst);
+    }
+
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        ALLOCATED.fetch_add(size, Ordering::SeqCst);
+        let current = CURRENT.fetch_add(size, Ordering::SeqCst) + size;
+        PEAK.fetch_max(current, Ordering::SeqCst);
+    }
+
+    /// Forwards to the system allocator while counting allocations.
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    #[cfg(feature = "count_allocations")]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| {
+            let a = vec![0_u8; 1000];
+            let b = vec![0_u8; 2000];
+            a.len() + b.len()
+        });
+
+        let stats = stats.unwrap();
+        assert_eq!(len, 3000);
+        assert!(stats.allocations >= 2);
+        assert!(stats.allocated_bytes >= 3000);
+        assert!(stats.peak_bytes >= 3000);
+    }
+
+    #[test]
+    #[cfg(not(feature = "count_allocations"))]
+    fn reports_nothing_without_allocator() {
+        assert_eq!(measure(|| 1), (1, None));
+    }
+}
@@ -0,0 +1,207 @@
+/// Requests to adventofcode.com, authenticated with the session cookie of the active profile.
+///
+/// Following the automation guidelines of adventofcode.com, requests identify this template in their `User-Agent`
+/// and each [`Endpoint`] is throttled to one request per [`Endpoint::min_interval`], across processes.
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{stdout, Write};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::{cache, session};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const CONTACT_ENV: &str = "AOC_CONTACT";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const REPOSITORY: &str = "https://github.com/fspoettel/advent-of-code-rust";
+const THROTTLE_FILE: &str = "requests";
+
+#[derive(Debug)]
+pub enum Error {
+    /// No session cookie is set for the active profile.
+    MissingSession,
+    /// The server rejected the session cookie, it is invalid or expired.
+    InvalidSession,
+    Status(u16, String),
+    Transport(String),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie is set for profile \"{}\". Run \"cargo auth set\" to set one.",
+                session::active_profile()
+            ),
+            Error::InvalidSession => write!(
+                f,
+                "the session cookie of profile \"{}\" is invalid or expired. Run \"cargo auth set\" to replace it.",
+                session::active_profile()
+            ),
+            Error::Status(status, url) => write!(f, "{url} responded with status {status}"),
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+/// The kinds of pages that are requested, each with its own throttle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endpoint {
+    Puzzle,
+    Input,
+    Settings,
+    /// Private leaderboards, which adventofcode.com asks to fetch at most every 15 minutes.
+    Leaderboard,
+}
+
+impl Endpoint {
+    fn name(self) -> &'static str {
+        match self {
+            Endpoint::Puzzle => "puzzle",
+            Endpoint::Input => "input",
+            Endpoint::Settings => "settings",
+            Endpoint::Leaderboard => "leaderboard",
+        }
+    }
+
+    /// The minimum time between two requests to this endpoint.
+    #[must_use]
+    pub fn min_interval(self) -> Duration {
+        match self {
+            Endpoint::Puzzle | Endpoint::Input => Duration::from_secs(5),
+            Endpoint::Settings => Duration::from_secs(2),
+            Endpoint::Leaderboard => Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+#[must_use]
+pub fn user_agent() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    match std::env::var(CONTACT_ENV) {
+        Ok(contact) if !contact.trim().is_empty() => {
+            format!(
+                "advent_of_code/{version} (+{REPOSITORY}; {})",
+                contact.trim()
+            )
+        }
+        _ => format!("advent_of_code/{version} (+{REPOSITORY})"),
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .redirects(0)
+        .user_agent(&user_agent())
+        .build()
+}
+
+fn millis_since_epoch(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// Reads the time of the last request to every endpoint, as `<endpoint> = <unix millis>` lines.
+fn read_last_requests() -> Vec<(String, u128)> {
+    let path = cache::get_dir().join(THROTTLE_FILE);
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (name, millis) = line.split_once(" = ")?;
+            Some((name.to_string(), millis.parse().ok()?))
+        })
+        .collect()
+}
+
+fn write_last_request(endpoint: Endpoint, at: SystemTime) {
+    let mut requests = read_last_requests();
+    requests.retain(|(name, _)| name != endpoint.name());
+    requests.push((endpoint.name().into(), millis_since_epoch(at)));
+
+    let contents: String = requests
+        .iter()
+        .map(|(name, millis)| format!("{name} = {millis}\n"))
+        .collect();
+
+    let dir = cache::get_dir();
+    // throttling is best-effort, a failed write only means that the next request isn't delayed.
+    let _ = fs::create_dir_all(&dir).and_then(|()| fs::write(dir.join(THROTTLE_FILE), contents));
+}
+
+/// How long to wait before the next request to `endpoint`.
+#[must_use]
+pub fn remaining_interval(endpoint: Endpoint, now: SystemTime) -> Duration {
+    let Some(last) = read_last_requests()
+        .into_iter()
+        .find_map(|(name, millis)| (name == endpoint.name()).then_some(millis))
+    else {
+        return Duration::ZERO;
+    };
+
+    let elapsed = millis_since_epoch(now).saturating_sub(last);
+    let interval = endpoint.min_interval().as_millis();
+    Duration::from_millis(u64::try_from(interval.saturating_sub(elapsed)).unwrap_or(u64::MAX))
+}
+
+/// Blocks until a request to `endpoint` is allowed and records it.
+fn wait_for_turn(endpoint: Endpoint) {
+    let remaining = remaining_interval(endpoint, SystemTime::now());
+    if !remaining.is_zero() {
+        print!(
+            "⏳ Waiting {:.1}s between requests to adventofcode.com… ",
+            remaining.as_secs_f64()
+        );
+        let _ = stdout().flush();
+        thread::sleep(remaining);
+        println!();
+    }
+    write_last_request(endpoint, SystemTime::now());
+}
+
+/// Fetches a page with the given session cookie. Pages that require a login redirect or answer with 400 otherwise.
+pub fn get_with_session(endpoint: Endpoint, path: &str, token: &str) -> Result<String, Error> {
+    wait_for_turn(endpoint);
+
+    let url = format!("{BASE_URL}{path}");
+    let response = agent()
+        .get(&url)
+        .set("Cookie", &format!("session={token}"))
+        .call();
+
+    match response {
+        Ok(response) if response.status() == 200 => response
+            .into_string()
+            .map_err(|e| Error::Transport(e.to_string())),
+        Ok(response) if response.status() < 400 => Err(Error::InvalidSession),
+        Ok(response) => Err(Error::Status(response.status(), url)),
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => Err(Error::InvalidSession),
+        Err(ureq::Error::Status(status, _)) => Err(Error::Status(status, url)),
+        Err(e) => Err(Error::Transport(e.to_string())),
+    }
+}
+
+/// Like [`get_with_session`], with the session cookie of the active profile.
+pub fn get(endpoint: Endpoint, path: &str) -> Result<String, Error> {
+    let token = session::load(&session::active_profile()).ok_or(Error::MissingSession)?;
+    get_with_session(endpoint, path, &token)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::user_agent;
+
+    #[test]
+    fn identifies_template() {
+        assert!(user_agent().starts_with("advent_of_code/"));
+        assert!(user_agent().contains("github.com/fspoettel/advent-of-code-rust"));
+    }
+}
@@ -0,0 +1,160 @@
+/// Where a solution reads its input from, see `cargo solve <day> --input <path>` and `--example [n]`.
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::{env, process};
+
+use crate::template::cli::{self, Matches};
+use crate::template::{encryption, get_data_dir, input_sets};
+use crate::{configured_year, Day};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Input {
+    /// `data/{year}/inputs/{day}.txt` of the configured event.
+    #[default]
+    Puzzle,
+    /// `data/examples/{day}.txt`, or `data/examples/{day}-{n}.txt` for a numbered example.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+    /// The input of another account, see [`input_sets`].
+    Set(String),
+}
+
+/// Stdin can only be read once, but child processes of [`crate::template::budget::supervise`] need it too.
+static STDIN: OnceLock<String> = OnceLock::new();
+
+const FLAGS: [&str; 3] = ["--input", "--example", "--input-set"];
+
+/// The puzzle input of a day of an event, e.g. `data/2023/inputs/01.txt`.
+#[must_use]
+pub fn get_path_for_puzzle(year: u16, day: Day) -> PathBuf {
+    get_data_dir(year).join("inputs").join(format!("{day}.txt"))
+}
+
+impl Input {
+    /// The input for `--input <path>`, where `-` means stdin.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        if path == "-" {
+            Input::Stdin
+        } else {
+            Input::File(path.into())
+        }
+    }
+
+    /// Reads `--input <path>`, `--example [n]` or `--input-set <name>`, which can't be combined.
+    pub fn from_matches(matches: &Matches) -> Result<Self, cli::Error> {
+        let given: Vec<_> = FLAGS
+            .into_iter()
+            .filter(|flag| matches.contains(flag))
+            .collect();
+
+        match given[..] {
+            [] => Ok(Input::Puzzle),
+            ["--input"] => Ok(Input::from_path(matches.value("--input").unwrap())),
+            ["--example"] => Ok(Input::Example(matches.value_from_str("--example")?)),
+            ["--input-set"] => Ok(Input::Set(matches.value("--input-set").unwrap().into())),
+            [a, b, ..] => Err(cli::Error::Conflict(a, b)),
+            [flag] => unreachable!("{flag} is not an input flag"),
+        }
+    }
+
+    /// The flag that selected this input.
+    #[must_use]
+    pub fn flag(&self) -> Option<&'static str> {
+        match self {
+            Input::Puzzle => None,
+            Input::Example(_) => Some("--example"),
+            Input::File(_) | Input::Stdin => Some("--input"),
+            Input::Set(_) => Some("--input-set"),
+        }
+    }
+
+    /// Whether this is the puzzle input, which answers and timings are recorded for.
+    #[must_use]
+    pub fn is_puzzle(&self) -> bool {
+        matches!(self, Input::Puzzle)
+    }
+
+    /// The arguments that pass this input on to a day binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::Puzzle => vec![],
+            Input::Example(None) => vec!["--example".into()],
+            Input::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Input::File(path) => vec!["--input".into(), path.display().to_string()],
+            Input::Stdin => vec!["--input".into(), "-".into()],
+            Input::Set(name) => vec!["--input-set".into(), name.clone()],
+        }
+    }
+
+    fn get_path(&self, day: Day) -> Option<PathBuf> {
+        let data = env::current_dir().unwrap().join("data");
+        match self {
+            Input::Puzzle => Some(get_path_for_puzzle(configured_year(), day)),
+            Input::Example(None) => Some(data.join("examples").join(format!("{day}.txt"))),
+            Input::Example(Some(n)) => Some(data.join("examples").join(format!("{day}-{n}.txt"))),
+            Input::File(path) => Some(path.clone()),
+            Input::Set(name) => Some(input_sets::get_path_for_input(name, day)),
+            Input::Stdin => None,
+        }
+    }
+
+    /// Reads the input for `day`, exiting if it can't be read. Encrypted inputs are decrypted, see [`encryption`].
+    #[must_use]
+    pub fn read(&self, day: Day) -> String {
+        let result = match self.get_path(day) {
+            None => read_stdin().map(String::from).map_err(|e| e.to_string()),
+            Some(path) => encryption::read_to_string(&path)
+                .map_err(|e| format!("\"{}\": {e}", path.display())),
+        };
+
+        result.unwrap_or_else(|e| {
+            eprintln!("Failed to read input: {e}");
+            process::exit(1);
+        })
+    }
+}
+
+/// Reads stdin to the end on the first call and returns the same contents afterwards.
+pub fn read_stdin() -> io::Result<&'static str> {
+    if let Some(contents) = STDIN.get() {
+        return Ok(contents);
+    }
+
+    let mut contents = String::new();
+    io::stdin().read_to_string(&mut contents)?;
+    Ok(STDIN.get_or_init(|| contents))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Input;
+
+    #[test]
+    fn reads_input_from_path() {
+        assert_eq!(Input::from_path("-"), Input::Stdin);
+        assert_eq!(
+            Input::from_path("edge.txt"),
+            Input::File(PathBuf::from("edge.txt"))
+        );
+    }
+
+    #[test]
+    fn converts_to_args() {
+        assert!(Input::Puzzle.to_args().is_empty());
+        assert_eq!(Input::Example(None).to_args(), ["--example"]);
+        assert_eq!(Input::Example(Some(2)).to_args(), ["--example", "2"]);
+        assert_eq!(Input::Stdin.to_args(), ["--input", "-"]);
+        assert_eq!(
+            Input::Set("alice".into()).to_args(),
+            ["--input-set", "alice"]
+        );
+    }
+}
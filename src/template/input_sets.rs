@@ -0,0 +1,148 @@
+/// Inputs and answers of other accounts, to catch solutions that only work for one input.
+///
+/// Every input set is a directory `data/inputs/<name>/`, holding inputs as `{day}.txt` and the answers that were
+/// accepted for them as `{day}.answers`. Answer files use the format of the progress files, e.g. `part_1.answer = 42`,
+/// so `data/progress/{day}.txt` can be shared as is.
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::{encryption, progress};
+use crate::Day;
+
+/// Output lines of an answer check start with `Part <n>: `, followed by one of these.
+pub const ANSWER_MATCHES: &str = "✔ matches the recorded answer";
+pub const ANSWER_DIFFERS: &str = "✖ differs from the recorded answer";
+pub const ANSWER_MISSING: &str = "no recorded answer";
+
+fn get_path_for_set(name: &str) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("inputs")
+        .join(name)
+}
+
+#[must_use]
+pub fn get_path_for_input(name: &str, day: Day) -> PathBuf {
+    get_path_for_set(name).join(format!("{day}.txt"))
+}
+
+#[must_use]
+pub fn get_path_for_answers(name: &str, day: Day) -> PathBuf {
+    get_path_for_set(name).join(format!("{day}.answers"))
+}
+
+/// The names of all input sets, in alphabetical order.
+#[must_use]
+pub fn list() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(env::current_dir().unwrap().join("data").join("inputs")) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+
+    names.sort();
+    names
+}
+
+#[must_use]
+pub fn has_input(name: &str, day: Day) -> bool {
+    encryption::exists(&get_path_for_input(name, day))
+}
+
+/// How an answer compares to the one recorded for an input set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Matches,
+    Differs { expected: String },
+    Missing,
+}
+
+impl Check {
+    /// Compares `answer` to the recorded answer of a part.
+    #[must_use]
+    pub fn new(expected: Option<&str>, answer: &str) -> Self {
+        match expected {
+            None => Check::Missing,
+            Some(expected) if expected == answer => Check::Matches,
+            Some(expected) => Check::Differs {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    /// Reads a check back from the output line of a part, see [`Check::line`].
+    #[must_use]
+    pub fn parse(line: &str, part: u8) -> Option<Self> {
+        let rest = line.strip_prefix(&format!("Part {part}: "))?;
+
+        if rest.starts_with(ANSWER_MATCHES) {
+            Some(Check::Matches)
+        } else if let Some(expected) = rest.strip_prefix(ANSWER_DIFFERS) {
+            Some(Check::Differs {
+                expected: expected.trim().to_string(),
+            })
+        } else if rest.starts_with(ANSWER_MISSING) {
+            Some(Check::Missing)
+        } else {
+            None
+        }
+    }
+
+    /// The output line of a part.
+    #[must_use]
+    pub fn line(&self, part: u8) -> String {
+        match self {
+            Check::Matches => format!("Part {part}: {ANSWER_MATCHES}"),
+            Check::Differs { expected } => format!("Part {part}: {ANSWER_DIFFERS} {expected}"),
+            Check::Missing => format!("Part {part}: {ANSWER_MISSING}"),
+        }
+    }
+}
+
+/// Compares `answer` to the recorded answer of an input set.
+#[must_use]
+pub fn check(name: &str, day: Day, part: u8, answer: &str) -> Check {
+    let answers = progress::load_from(&get_path_for_answers(name, day));
+    Check::new(answers.part(part).answer.as_deref(), answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Check;
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(Check::new(Some("42"), "42"), Check::Matches);
+        assert_eq!(
+            Check::new(Some("42"), "43"),
+            Check::Differs {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(Check::new(None, "42"), Check::Missing);
+    }
+
+    #[test]
+    fn round_trips_lines() {
+        let checks = [
+            Check::Matches,
+            Check::Differs {
+                expected: "13133452426987".into(),
+            },
+            Check::Missing,
+        ];
+
+        for check in checks {
+            assert_eq!(Check::parse(&check.line(2), 2), Some(check.clone()));
+            assert_eq!(Check::parse(&check.line(2), 1), None);
+        }
+        assert_eq!(Check::parse("Part 1: 42 (1.0µs)", 1), None);
+    }
+}
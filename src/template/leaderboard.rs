@@ -0,0 +1,310 @@
+/// Private leaderboards, parsed from the JSON of `/{year}/leaderboard/private/view/{id}.json`.
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use serde_json::Value;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GOLD: &str = "\x1b[33m";
+const ANSI_SILVER: &str = "\x1b[37m";
+const ANSI_GREEN: &str = "\x1b[32m";
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    /// A field is missing or has the wrong type.
+    Format(&'static str),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Json(e) => write!(f, "invalid JSON: {e}"),
+            Error::Format(field) => {
+                write!(f, "not a leaderboard, \"{field}\" is missing or invalid")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// Members without a name are shown as anonymous users, like on the website.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// When the stars of both parts of a day were collected, in seconds since the unix epoch.
+    pub completions: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub owner_id: u64,
+    /// Sorted by local score, highest first.
+    pub members: Vec<Member>,
+}
+
+/// Reads a number, which older leaderboards store as a string.
+fn as_u64(value: &Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+fn field<'a>(value: &'a Value, name: &'static str) -> Result<&'a Value, Error> {
+    value.get(name).ok_or(Error::Format(name))
+}
+
+fn number(value: &Value, name: &'static str) -> Result<u64, Error> {
+    as_u64(field(value, name)?).ok_or(Error::Format(name))
+}
+
+fn parse_completions(value: &Value) -> Result<BTreeMap<u8, [Option<u64>; 2]>, Error> {
+    let days = field(value, "completion_day_level")?
+        .as_object()
+        .ok_or(Error::Format("completion_day_level"))?;
+
+    days.iter()
+        .map(|(day, parts)| {
+            let day = day
+                .parse()
+                .map_err(|_| Error::Format("completion_day_level"))?;
+            let star = |part: &str| -> Result<Option<u64>, Error> {
+                parts
+                    .get(part)
+                    .map(|star| number(star, "get_star_ts"))
+                    .transpose()
+            };
+            Ok((day, [star("1")?, star("2")?]))
+        })
+        .collect()
+}
+
+fn parse_member(value: &Value) -> Result<Member, Error> {
+    Ok(Member {
+        id: number(value, "id")?,
+        name: value.get("name").and_then(Value::as_str).map(String::from),
+        local_score: number(value, "local_score")?,
+        stars: number(value, "stars")?,
+        completions: parse_completions(value)?,
+    })
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, Error> {
+    let value: Value = serde_json::from_str(json).map_err(Error::Json)?;
+
+    let mut members = field(&value, "members")?
+        .as_object()
+        .ok_or(Error::Format("members"))?
+        .values()
+        .map(parse_member)
+        .collect::<Result<Vec<_>, _>>()?;
+    members.sort_by(|a, b| (b.local_score, b.stars, a.id).cmp(&(a.local_score, a.stars, b.id)));
+
+    Ok(Leaderboard {
+        year: number(&value, "event")?
+            .try_into()
+            .map_err(|_| Error::Format("event"))?,
+        owner_id: number(&value, "owner_id")?,
+        members,
+    })
+}
+
+/// e.g. `01:02:03`, hours are not wrapped into days.
+#[must_use]
+pub fn format_duration(secs: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+fn format_delta(member: &Member, previous: Option<&Leaderboard>) -> String {
+    let Some(previous) = previous else {
+        return String::new();
+    };
+
+    let Some(before) = previous.members.iter().find(|m| m.id == member.id) else {
+        return format!("  {ANSI_GREEN}new{ANSI_RESET}");
+    };
+
+    let score = member.local_score.saturating_sub(before.local_score);
+    let stars = member.stars.saturating_sub(before.stars);
+    if score == 0 && stars == 0 {
+        String::new()
+    } else {
+        format!("  {ANSI_GREEN}+{score} (+{stars}★){ANSI_RESET}")
+    }
+}
+
+/// A star matrix of the first `unlocked` days, with the local scores and the changes since `previous`.
+#[must_use]
+pub fn render_matrix(
+    leaderboard: &Leaderboard,
+    previous: Option<&Leaderboard>,
+    days: u8,
+    unlocked: u8,
+) -> Vec<String> {
+    let prefix = " ".repeat(12);
+    let tens: String = (1..=days)
+        .map(|day| match day / 10 {
+            0 => ' ',
+            n => char::from(b'0' + n),
+        })
+        .collect();
+    let units: String = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+
+    let mut lines = vec![
+        format!("{prefix}{}", tens.trim_end()),
+        format!("{prefix}{units}"),
+    ];
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let matrix: String = (1..=days)
+            .map(|day| {
+                let stars = member.completions.get(&day);
+                match stars {
+                    _ if day > unlocked => " ".into(),
+                    Some([Some(_), Some(_)]) => format!("{ANSI_GOLD}★{ANSI_RESET}"),
+                    Some([Some(_), None]) => format!("{ANSI_SILVER}☆{ANSI_RESET}"),
+                    _ => "·".into(),
+                }
+            })
+            .collect();
+
+        lines.push(format!(
+            "{:>3}) {:>5} {matrix} {}{}",
+            rank + 1,
+            member.local_score,
+            member.display_name(),
+            format_delta(member, previous)
+        ));
+    }
+
+    lines
+}
+
+/// The time it took members to collect the stars of a day, relative to `unlock` in seconds since the unix epoch.
+#[must_use]
+pub fn render_day(leaderboard: &Leaderboard, day: u8, unlock: u64) -> Vec<String> {
+    let mut times: Vec<_> = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| {
+            let [first, second] = member.completions.get(&day)?;
+            let since_unlock = |ts: &Option<u64>| ts.map(|ts| ts.saturating_sub(unlock));
+            Some((since_unlock(second), since_unlock(first)?, member))
+        })
+        .collect();
+    // members with both stars first, then by time.
+    times.sort_by_key(|(second, first, _)| (second.is_none(), *second, *first));
+
+    let mut lines = vec![format!("{ANSI_BOLD}       Part 1      Part 2{ANSI_RESET}")];
+    for (rank, (second, first, member)) in times.iter().enumerate() {
+        lines.push(format!(
+            "{:>3})  {}  {:>10}  {}",
+            rank + 1,
+            format_duration(*first),
+            second.map_or_else(|| "-".into(), format_duration),
+            member.display_name()
+        ));
+    }
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, parse, render_day, render_matrix, Error};
+
+    const CURRENT: &str = include_str!("fixtures/leaderboard.json");
+    const PREVIOUS: &str = include_str!("fixtures/leaderboard_previous.json");
+
+    /// Unlock of day 1 of 2023.
+    const UNLOCK: u64 = 1_701_406_800;
+
+    #[test]
+    fn parses_leaderboard() {
+        let leaderboard = parse(CURRENT).unwrap();
+
+        assert_eq!(leaderboard.year, 2023);
+        assert_eq!(leaderboard.owner_id, 1001);
+
+        let names: Vec<_> = leaderboard
+            .members
+            .iter()
+            .map(super::Member::display_name)
+            .collect();
+        assert_eq!(names, ["alice", "bob", "(anonymous user #1003)"]);
+
+        let alice = &leaderboard.members[0];
+        assert_eq!((alice.local_score, alice.stars), (27, 5));
+        assert_eq!(
+            alice.completions[&1],
+            [Some(1_701_407_532), Some(1_701_408_000)]
+        );
+        assert_eq!(alice.completions[&3], [Some(1_701_580_000), None]);
+    }
+
+    #[test]
+    fn rejects_other_json() {
+        assert!(matches!(
+            parse("{\"members\": {}}"),
+            Err(Error::Format("event"))
+        ));
+        assert!(matches!(parse("<html>"), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn renders_matrix_with_deltas() {
+        let current = parse(CURRENT).unwrap();
+        let previous = parse(PREVIOUS).unwrap();
+
+        assert_eq!(
+            render_matrix(&current, Some(&previous), 12, 4),
+            [
+                "                     111",
+                "            123456789012",
+                "  1)    27 \x1b[33m★\x1b[0m\x1b[33m★\x1b[0m\x1b[37m☆\x1b[0m·         alice  \x1b[32m+7 (+1★)\x1b[0m",
+                "  2)    24 \x1b[33m★\x1b[0m\x1b[33m★\x1b[0m··         bob",
+                "  3)     1 \x1b[37m☆\x1b[0m···         (anonymous user #1003)  \x1b[32mnew\x1b[0m",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_times_since_unlock() {
+        assert_eq!(
+            render_day(&parse(CURRENT).unwrap(), 1, UNLOCK),
+            [
+                "\x1b[1m       Part 1      Part 2\x1b[0m",
+                "  1)  00:05:00    00:10:00  bob",
+                "  2)  00:12:12    00:20:00  alice",
+                "  3)  25:53:20           -  (anonymous user #1003)",
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_duration() {
+        assert_eq!(format_duration(59), "00:00:59");
+        assert_eq!(format_duration(93_200), "25:53:20");
+    }
+}
@@ -1,22 +1,52 @@
 use crate::Day;
-use std::{env, fs};
+use std::env;
+use std::path::PathBuf;
 
+pub mod answer;
 pub mod aoc_cli;
+pub mod budget;
+pub mod cache;
+pub mod cli;
 pub mod commands;
+pub mod differential;
+pub mod encryption;
+pub mod export;
+pub mod flamegraph;
+pub mod heap;
+pub mod http;
+pub mod input;
+pub mod input_sets;
+pub mod leaderboard;
+pub mod progress;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod render;
 pub mod runner;
+pub mod selection;
+pub mod session;
+pub mod trace;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// The directory of the inputs and descriptions of an event, e.g. `data/2023`.
+#[must_use]
+pub fn get_data_dir(year: u16) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join(year.to_string())
+}
+
+/// Helper function that reads a text file to a string. Encrypted files are decrypted, see [`encryption`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = encryption::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -27,22 +57,69 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let f = encryption::read_to_string(&filepath);
+    f.unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Pass `parse = <fn>` to additionally time a parser that both parts share, e.g. `solution!(4, parse = parse_cards)`.
+/// Pass `variants = { ... }` to register alternative implementations of a part, which `solve --variant <name>` and
+/// `solve --all-variants` run. `part_one` and `part_two` are the `default` variants.
+///
+/// ```ignore
+/// advent_of_code::solution!(6, variants = {
+///     part_one: [closed_form => part_one_closed_form],
+///     part_two: [closed_form => part_two_closed_form],
+/// });
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (
+        $day:expr
+        $(, parse = $parse:expr)?
+        $(, variants = {
+            $(part_one: [$($name_1:ident => $func_1:path),* $(,)?] $(,)?)?
+            $(part_two: [$($name_2:ident => $func_2:path),* $(,)?] $(,)?)?
+        })?
+        $(,)?
+    ) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let options = Options::from_env();
+            advent_of_code::template::trace::init(DAY, &options);
+            check_variant(
+                &[
+                    "default",
+                    $($($(stringify!($name_1),)*)?)?
+                    $($($(stringify!($name_2),)*)?)?
+                ],
+                &options,
+            );
+            let input = options.input.read(DAY);
+            $(run_parse($parse, input.as_str(), &options);)?
+            run_variants(
+                &[
+                    ("default", part_one as fn(&str) -> _),
+                    $($($((stringify!($name_1), $func_1 as fn(&str) -> _),)*)?)?
+                ],
+                &input,
+                DAY,
+                1,
+                &options,
+            );
+            run_variants(
+                &[
+                    ("default", part_two as fn(&str) -> _),
+                    $($($((stringify!($name_2), $func_2 as fn(&str) -> _),)*)?)?
+                ],
+                &input,
+                DAY,
+                2,
+                &options,
+            );
         }
     };
 }
@@ -0,0 +1,182 @@
+/// Local records of accepted answers and best timings, stored in `data/progress/{day}.txt`.
+///
+/// The files contain one `key = value` pair per line, e.g. `part_1.answer = 42` or `part_2.best_nanos = 1200`.
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::Day;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PartProgress {
+    /// The answer that was accepted by advent of code.
+    pub answer: Option<String>,
+    /// The fastest average time of a timed release run.
+    pub best_time: Option<Duration>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub parts: [PartProgress; 2],
+}
+
+impl DayProgress {
+    #[must_use]
+    pub fn part(&self, part: u8) -> &PartProgress {
+        &self.parts[usize::from(part.clamp(1, 2) - 1)]
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartProgress {
+        &mut self.parts[usize::from(part.clamp(1, 2) - 1)]
+    }
+
+    /// The number of parts with an accepted answer.
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.parts.iter().filter(|p| p.answer.is_some()).count()
+    }
+
+    /// The sum of the best times of both parts, or of the one part that has a time.
+    #[must_use]
+    pub fn best_total_time(&self) -> Option<Duration> {
+        let times: Vec<_> = self.parts.iter().filter_map(|p| p.best_time).collect();
+        if times.is_empty() {
+            None
+        } else {
+            Some(times.iter().sum())
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        let mut progress = Self::default();
+
+        for line in s.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let Some((part, field)) = key.split_once('.') else {
+                continue;
+            };
+            let part = match part {
+                "part_1" => progress.part_mut(1),
+                "part_2" => progress.part_mut(2),
+                _ => continue,
+            };
+
+            match field {
+                "answer" => part.answer = Some(value.to_string()),
+                "best_nanos" => {
+                    part.best_time = value.parse().ok().map(Duration::from_nanos);
+                }
+                _ => {}
+            }
+        }
+
+        progress
+    }
+
+    fn serialize(&self) -> String {
+        let mut lines = vec![];
+
+        for (i, part) in self.parts.iter().enumerate() {
+            if let Some(answer) = &part.answer {
+                lines.push(format!("part_{}.answer = {answer}", i + 1));
+            }
+            if let Some(time) = part.best_time {
+                lines.push(format!("part_{}.best_nanos = {}", i + 1, time.as_nanos()));
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+#[must_use]
+pub fn get_path_for_progress(day: Day) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("progress")
+        .join(format!("{day}.txt"))
+}
+
+/// Reads the progress of a day. Days without records have no progress.
+#[must_use]
+pub fn load(day: Day) -> DayProgress {
+    load_from(&get_path_for_progress(day))
+}
+
+/// Reads a progress file, e.g. the answers of a profile. Missing files have no progress.
+#[must_use]
+pub fn load_from(path: &Path) -> DayProgress {
+    fs::read_to_string(path)
+        .map(|s| DayProgress::parse(&s))
+        .unwrap_or_default()
+}
+
+pub fn save(day: Day, progress: &DayProgress) -> Result<(), io::Error> {
+    let path = get_path_for_progress(day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, progress.serialize())
+}
+
+/// Records the answer to a part that was accepted.
+pub fn record_answer(day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    let mut progress = load(day);
+    progress.part_mut(part).answer = Some(answer.to_string());
+    save(day, &progress)
+}
+
+/// Records a timing for a part if it is faster than the previous best.
+pub fn record_time(day: Day, part: u8, time: Duration) -> Result<(), io::Error> {
+    let mut progress = load(day);
+    let best = &mut progress.part_mut(part).best_time;
+
+    if best.is_some_and(|best| best <= time) {
+        return Ok(());
+    }
+
+    *best = Some(time);
+    save(day, &progress)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DayProgress, PartProgress};
+
+    #[test]
+    fn round_trips_progress() {
+        let progress = DayProgress {
+            parts: [
+                PartProgress {
+                    answer: Some("42".into()),
+                    best_time: Some(Duration::from_nanos(1200)),
+                },
+                PartProgress {
+                    answer: None,
+                    best_time: Some(Duration::from_micros(5)),
+                },
+            ],
+        };
+
+        let parsed = DayProgress::parse(&progress.serialize());
+        assert_eq!(parsed, progress);
+        assert_eq!(parsed.stars(), 1);
+        assert_eq!(parsed.best_total_time(), Some(Duration::from_nanos(6200)));
+    }
+
+    #[test]
+    fn ignores_unknown_lines() {
+        let parsed = DayProgress::parse("# comment\npart_3.answer = 1\npart_1.answer = ABC\n");
+        assert_eq!(parsed.part(1).answer.as_deref(), Some("ABC"));
+        assert_eq!(parsed.stars(), 1);
+    }
+}
@@ -0,0 +1,380 @@
+/// Puzzle descriptions, converted from the pages of adventofcode.com to markdown in `data/{year}/puzzles/{day}.md`.
+///
+/// When a description changes, e.g. once part two unlocks, the replaced version is kept in
+/// `data/{year}/puzzles/{day}.previous.md` so that `cargo read` can highlight what was added.
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::template::cache::{self, Entry};
+use crate::template::get_data_dir;
+use crate::template::http::{Endpoint, BASE_URL};
+use crate::Day;
+
+const ARTICLE_START: &str = "<article class=\"day-desc\">";
+const ARTICLE_END: &str = "</article>";
+
+#[must_use]
+pub fn get_path(year: u16, day: Day) -> PathBuf {
+    get_data_dir(year).join("puzzles").join(format!("{day}.md"))
+}
+
+/// The description that [`save`] replaced.
+#[must_use]
+pub fn get_path_for_previous(year: u16, day: Day) -> PathBuf {
+    get_path(year, day).with_extension("previous.md")
+}
+
+/// The path of a puzzle page on adventofcode.com.
+#[must_use]
+pub fn get_url_path(year: u16, day: Day) -> String {
+    format!("/{year}/day/{}", day.into_inner())
+}
+
+/// The path of a puzzle input on adventofcode.com.
+#[must_use]
+pub fn get_input_url_path(year: u16, day: Day) -> String {
+    format!("{}/input", get_url_path(year, day))
+}
+
+/// Converts the descriptions of a puzzle page to markdown. Returns `None` if the page has no description.
+#[must_use]
+pub fn to_markdown(html: &str) -> Option<String> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find(ARTICLE_START) {
+        let body = &rest[start + ARTICLE_START.len()..];
+        let end = body.find(ARTICLE_END).unwrap_or(body.len());
+        articles.push(Converter::default().convert(&body[..end]));
+        rest = &body[end..];
+    }
+
+    if articles.is_empty() {
+        None
+    } else {
+        Some(articles.join("\n"))
+    }
+}
+
+/// Writes a description and keeps the one it replaces, if that differs. Returns whether the description changed.
+pub fn save(year: u16, day: Day, markdown: &str) -> io::Result<bool> {
+    let path = get_path(year, day);
+    let current = fs::read_to_string(&path).ok();
+
+    if current.as_deref() == Some(markdown) {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    if let Some(current) = current {
+        fs::write(get_path_for_previous(year, day), current)?;
+    }
+    fs::write(&path, markdown)?;
+    Ok(true)
+}
+
+/// Writes the description of a day from its cached page, or fetches the page if it isn't cached or `refresh` is set.
+pub fn download(year: u16, day: Day, refresh: bool) -> Result<Entry, String> {
+    let entry = cache::fetch(Endpoint::Puzzle, &get_url_path(year, day), refresh)
+        .map_err(|e| e.to_string())?;
+    let markdown = to_markdown(&entry.body).ok_or("the puzzle page contains no description")?;
+
+    save(year, day, &markdown)
+        .map_err(|e| format!("could not write \"{}\": {e}", get_path(year, day).display()))?;
+    Ok(entry)
+}
+
+/// The description of one part. Part two starts at its heading and is missing until part one is solved.
+#[must_use]
+pub fn get_part(markdown: &str, part: u8) -> Option<&str> {
+    let start = markdown
+        .match_indices('#')
+        .map(|(i, _)| i)
+        .filter(|&i| i == 0 || markdown[..i].ends_with('\n'))
+        .find(|&i| markdown[i..].lines().next().unwrap().contains("Part Two"));
+
+    match (part, start) {
+        (1, Some(start)) => Some(markdown[..start].trim_end()),
+        (1, None) => Some(markdown.trim_end()),
+        (2, Some(start)) => Some(markdown[start..].trim_end()),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct Converter {
+    out: String,
+    in_pre: bool,
+    in_code: bool,
+    /// Where the current inline code starts, and whether an `<em>` directly follows it.
+    code_start: usize,
+    code_em: bool,
+    em_end: usize,
+    links: Vec<Option<String>>,
+}
+
+impl Converter {
+    fn convert(mut self, html: &str) -> String {
+        let mut rest = html;
+
+        while let Some(start) = rest.find('<') {
+            self.text(&rest[..start]);
+            let Some(end) = rest[start..].find('>') else {
+                break;
+            };
+            self.tag(&rest[start + 1..start + end]);
+            rest = &rest[start + end + 1..];
+        }
+        self.text(rest);
+
+        self.out.trim_end().to_string() + "\n"
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with('\n')
+    }
+
+    fn text(&mut self, raw: &str) {
+        let text = decode_entities(raw);
+
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+
+        if text.trim().is_empty() && self.at_line_start() {
+            return;
+        }
+
+        let mut last_was_space = self.out.ends_with(' ') || self.at_line_start();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !last_was_space {
+                    self.out.push(' ');
+                }
+                last_was_space = true;
+            } else {
+                self.out.push(c);
+                last_was_space = false;
+            }
+        }
+    }
+
+    fn end_block(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() {
+            self.out.push_str(if self.out.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        while self.out.ends_with("\n\n\n") {
+            self.out.pop();
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+
+        match (name.to_ascii_lowercase().as_str(), closing) {
+            ("h2", false) => self.out.push_str("## "),
+            ("h2" | "p" | "ul", true) => self.end_block(),
+            ("pre", false) => {
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            ("pre", true) => {
+                if !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.out.push_str("```");
+                self.in_pre = false;
+                self.end_block();
+            }
+            ("code", _) if self.in_pre => {}
+            ("code", false) => {
+                self.code_start = self.out.len();
+                self.code_em = false;
+                self.out.push('`');
+                self.in_code = true;
+            }
+            ("code", true) => {
+                // answers are highlighted as `<code><em>142</em></code>`, which markdown writes as *`142`*.
+                if self.code_em && self.em_end == self.out.len() {
+                    self.out.insert(self.code_start, '*');
+                    self.out.push_str("`*");
+                } else {
+                    self.out.push('`');
+                }
+                self.in_code = false;
+            }
+            ("em", false) if self.in_code => {
+                self.code_em = self.out.len() == self.code_start + 1;
+            }
+            ("em", true) if self.in_code => self.em_end = self.out.len(),
+            ("em", _) if self.in_pre => {}
+            ("em", _) => self.out.push('*'),
+            ("li", false) => self.out.push_str("- "),
+            ("li", true) => {
+                let trimmed = self.out.trim_end_matches(' ').len();
+                self.out.truncate(trimmed);
+                self.out.push('\n');
+            }
+            ("br", _) => self.out.push('\n'),
+            ("a", false) => {
+                let href = attribute(attributes, "href").map(|href| {
+                    if href.starts_with('/') {
+                        format!("{BASE_URL}{href}")
+                    } else {
+                        href
+                    }
+                });
+                if href.is_some() {
+                    self.out.push('[');
+                }
+                self.links.push(href);
+            }
+            ("a", true) => {
+                if let Some(Some(href)) = self.links.pop() {
+                    self.out.push_str(&format!("]({href})"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let start = attributes.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = attributes[start..].find('"')?;
+    Some(decode_entities(&attributes[start..start + end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, get_part, to_markdown};
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a map; on it, they've used <em class="star">stars</em> to mark the top fifty locations.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+</code></pre>
+<p>In this example, the calibration values are <code>12</code>, <code>38</code> and <code><em>15</em></code>. Adding these together produces <code><em>142</em></code>.</p>
+<ul>
+<li>Read the <a href="/2023/about">about page</a>.</li>
+<li>Values can be &lt;= 9 &amp; &gt;= 1.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>54304</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Your calculation isn't quite right.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn converts_descriptions() {
+        assert_eq!(
+            to_markdown(PAGE).unwrap(),
+            "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a map; on it, they've used *stars* to mark the top fifty locations.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+```
+
+In this example, the calibration values are `12`, `38` and *`15`*. Adding these together produces *`142`*.
+
+- Read the [about page](https://adventofcode.com/2023/about).
+- Values can be <= 9 & >= 1.
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+"
+        );
+    }
+
+    #[test]
+    fn splits_parts() {
+        let markdown = to_markdown(PAGE).unwrap();
+
+        assert!(get_part(&markdown, 1)
+            .unwrap()
+            .ends_with("- Values can be <= 9 & >= 1."));
+        assert_eq!(
+            get_part(&markdown, 2),
+            Some("## --- Part Two ---\n\nYour calculation isn't quite right.")
+        );
+
+        let part_one = get_part(&markdown, 1).unwrap();
+        assert_eq!(get_part(part_one, 1), Some(part_one));
+        assert_eq!(get_part(part_one, 2), None);
+    }
+
+    #[test]
+    fn ignores_pages_without_description() {
+        assert_eq!(to_markdown("<main><p>Please log in.</p></main>"), None);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&#39;&#x2F; &bogus"),
+            "<a> &'/ &bogus"
+        );
+    }
+}
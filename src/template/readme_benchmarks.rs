@@ -1,8 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, io};
 
-use crate::Day;
+use crate::template::aoc_cli;
+use crate::template::heap::{format_bytes, HeapStats};
+use crate::{current_year, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -18,17 +24,162 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Statistics of a benched run, as printed by the runner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    /// The mean time, formatted.
+    pub time: String,
+    pub nanos: f64,
+    pub stddev_nanos: f64,
+    pub samples: u64,
+    /// Every sample in nanoseconds, only collected when exporting.
+    pub raw_nanos: Vec<u64>,
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Only present for days that declare a parser in `solution!`.
+    pub parse: Option<Stats>,
+    pub stats: [Option<Stats>; 2],
+    /// Only present for runs with the counting allocator.
+    pub heap: [Option<HeapStats>; 2],
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
+}
+
+/// Optional columns of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Samples,
+    Stddev,
+    Share,
+    Chart,
+    Links,
+    Heap,
+}
+
+impl Column {
+    pub const ALL: [Column; 7] = [
+        Column::Parse,
+        Column::Samples,
+        Column::Stddev,
+        Column::Share,
+        Column::Chart,
+        Column::Links,
+        Column::Heap,
+    ];
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "samples" => Ok(Column::Samples),
+            "stddev" => Ok(Column::Stddev),
+            "share" => Ok(Column::Share),
+            "chart" => Ok(Column::Chart),
+            "links" => Ok(Column::Links),
+            "heap" => Ok(Column::Heap),
+            x => Err(format!(
+                "unknown column \"{x}\", expected one of: parse, samples, stddev, share, chart, links, heap or all"
+            )),
+        }
+    }
+}
+
+/// Parses a comma separated list of columns, e.g. `parse,share,chart`. `all` selects every column.
+pub fn parse_columns(s: &str) -> Result<Vec<Column>, String> {
+    if s.trim() == "all" {
+        return Ok(Column::ALL.to_vec());
+    }
+    s.split(',').map(str::parse).collect()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            x => Err(format!("unknown format \"{x}\", expected markdown or html")),
+        }
+    }
+}
+
+/// How the benchmark table is written. The default is the plain `| Day | Part 1 | Part 2 |` table.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub columns: Vec<Column>,
+    pub format: Format,
+    /// Adds the machine, rustc version and commit hash below the table header.
+    pub metadata: bool,
+    /// Writes a standalone SVG bar chart to this path.
+    pub svg: Option<PathBuf>,
+}
+
+impl Config {
+    fn has(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+/// The environment the benchmarks ran in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Metadata {
+    pub machine: String,
+    pub rustc: String,
+    pub commit: String,
+}
+
+impl Metadata {
+    #[must_use]
+    pub fn collect() -> Self {
+        let command = |program: &str, args: &[&str]| {
+            Command::new(program)
+                .args(args)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+
+        let cpu = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find(|l| l.starts_with("model name"))
+                    .and_then(|l| l.split_once(':'))
+                    .map(|(_, name)| name.trim().to_string())
+            })
+            .or_else(|| command("sysctl", &["-n", "machdep.cpu.brand_string"]))
+            .unwrap_or_else(|| "unknown CPU".into());
+
+        Metadata {
+            machine: format!("{cpu} ({}/{})", env::consts::OS, env::consts::ARCH),
+            rustc: command("rustc", &["--version"]).unwrap_or_else(|| "unknown rustc".into()),
+            commit: command("git", &["rev-parse", "--short", "HEAD"])
+                .unwrap_or_else(|| "unknown commit".into()),
+        }
+    }
 }
 
 #[must_use]
@@ -36,8 +187,9 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the section between the first and last occurrence of `marker`.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,78 +210,413 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+enum Cell {
+    Text(String),
+    Code(String),
+    Link(String, String),
+}
+
+impl Cell {
+    fn render(&self, format: Format) -> String {
+        match (self, format) {
+            (Cell::Text(s), _) => s.clone(),
+            (Cell::Code(s), Format::Markdown) => format!("`{s}`"),
+            (Cell::Code(s), Format::Html) => format!("<code>{s}</code>"),
+            (Cell::Link(text, url), Format::Markdown) => format!("[{text}]({url})"),
+            (Cell::Link(text, url), Format::Html) => format!("<a href=\"{url}\">{text}</a>"),
+        }
+    }
+}
+
+/// The header and the rows of the table, depending on the configured columns.
+fn build_rows(
+    timings: &[Timings],
+    total_millis: f64,
+    year: u16,
+    config: &Config,
+) -> (Vec<&'static str>, Vec<Vec<Cell>>) {
+    let mut header = vec!["Day"];
+    if config.has(Column::Links) {
+        header.push("Puzzle");
+    }
+    if config.has(Column::Parse) {
+        header.push("Parse");
+    }
+    header.extend(["Part 1", "Part 2"]);
+    if config.has(Column::Samples) {
+        header.push("Samples");
+    }
+    if config.has(Column::Heap) {
+        header.extend(["Peak heap", "Allocations"]);
+    }
+    if config.has(Column::Share) {
+        header.push("Share");
+    }
+    if config.has(Column::Chart) {
+        header.push("Chart");
+    }
+
+    let max_nanos = timings.iter().map(|t| t.total_nanos).fold(0_f64, f64::max);
+
+    let rows = timings
+        .iter()
+        .map(|timing| {
+            let day = timing.day.into_inner();
+            let mut row = vec![Cell::Link(
+                format!("Day {day}"),
+                get_path_for_bin(timing.day),
+            )];
+
+            if config.has(Column::Links) {
+                row.push(Cell::Link(
+                    "Puzzle".into(),
+                    format!("https://adventofcode.com/{year}/day/{day}"),
+                ));
+            }
+
+            if config.has(Column::Parse) {
+                row.push(Cell::Code(
+                    timing
+                        .parse
+                        .as_ref()
+                        .map_or_else(|| "-".into(), |s| format_stats(s, config)),
+                ));
+            }
+
+            for (part, stats) in [&timing.part_1, &timing.part_2].iter().zip(&timing.stats) {
+                let text = match (part, stats) {
+                    (Some(_), Some(stats)) => format_stats(stats, config),
+                    (Some(part), None) => part.clone(),
+                    (None, _) => "-".into(),
+                };
+                row.push(Cell::Code(text));
+            }
+
+            if config.has(Column::Samples) {
+                let samples = timing
+                    .stats
+                    .iter()
+                    .map(|s| {
+                        s.as_ref()
+                            .map_or_else(|| "-".into(), |s| s.samples.to_string())
+                    })
+                    .collect::<Vec<_>>();
+                row.push(Cell::Text(samples.join(" / ")));
+            }
+
+            if config.has(Column::Heap) {
+                let per_part = |f: fn(&HeapStats) -> String| {
+                    let cells: Vec<_> = timing
+                        .heap
+                        .iter()
+                        .map(|h| h.as_ref().map_or_else(|| "-".into(), f))
+                        .collect();
+                    Cell::Text(cells.join(" / "))
+                };
+                row.push(per_part(|h| format_bytes(h.peak_bytes)));
+                row.push(per_part(|h| {
+                    format!("{} ({})", h.allocations, format_bytes(h.allocated_bytes))
+                }));
+            }
+
+            if config.has(Column::Share) {
+                let share = if total_millis > 0_f64 {
+                    timing.total_nanos / (total_millis * 10_000_f64)
+                } else {
+                    0_f64
+                };
+                row.push(Cell::Text(format!("{share:.1}%")));
+            }
+
+            if config.has(Column::Chart) {
+                let ratio = if max_nanos > 0_f64 {
+                    timing.total_nanos / max_nanos
+                } else {
+                    0_f64
+                };
+                row.push(Cell::Text(chart_bar(ratio, CHART_WIDTH)));
+            }
+
+            row
+        })
+        .collect();
+
+    (header, rows)
+}
+
+fn format_stats(stats: &Stats, config: &Config) -> String {
+    if config.has(Column::Stddev) && stats.samples > 1 {
+        format!("{} ± {}", stats.time, format_nanos(stats.stddev_nanos))
+    } else {
+        stats.time.clone()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+const CHART_WIDTH: usize = 20;
+
+/// A horizontal bar of `ratio * width` characters, using eighth blocks for the fraction.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn chart_bar(ratio: f64, width: usize) -> String {
+    const EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = (ratio.clamp(0_f64, 1_f64) * (width * 8) as f64).round() as usize;
+    let (full, rest) = (eighths / 8, eighths % 8);
+    let mut bar = "█".repeat(full);
+    if rest > 0 {
+        bar.push(EIGHTHS[rest]);
+    }
+    if bar.is_empty() {
+        // keep tiny timings visible.
+        bar.push('▏');
+    }
+    bar
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: &[Timings],
+    total_millis: f64,
+    year: u16,
+    config: &Config,
+    metadata: Option<&Metadata>,
+) -> String {
+    let (header, rows) = build_rows(timings, total_millis, year, config);
+    let format = config.format;
+
+    let mut lines: Vec<String> = vec![MARKER.into()];
+
+    let metadata = metadata.map(|m| format!("{} · {} · commit {}", m.machine, m.rustc, m.commit));
+
+    match format {
+        Format::Markdown => {
+            lines.push(format!("{prefix} Benchmarks"));
+            lines.push(String::new());
+            if let Some(metadata) = metadata {
+                lines.push(format!("_{metadata}_"));
+                lines.push(String::new());
+            }
+            lines.push(format!("| {} |", header.join(" | ")));
+            lines.push(format!("| {}  |", vec![":---:"; header.len()].join(" | ")));
+            for row in &rows {
+                let cells: Vec<_> = row.iter().map(|c| c.render(format)).collect();
+                lines.push(format!("| {} |", cells.join(" | ")));
+            }
+            lines.push(String::new());
+            lines.push(format!("**Total: {total_millis:.2}ms**"));
+        }
+        Format::Html => {
+            let level = prefix.len();
+            lines.push(format!("<h{level}>Benchmarks</h{level}>"));
+            if let Some(metadata) = metadata {
+                lines.push(format!("<p><em>{metadata}</em></p>"));
+            }
+            lines.push("<table>".into());
+            let cells: Vec<_> = header.iter().map(|h| format!("<th>{h}</th>")).collect();
+            lines.push(format!("  <tr>{}</tr>", cells.join("")));
+            for row in &rows {
+                let cells: Vec<_> = row
+                    .iter()
+                    .map(|c| format!("<td>{}</td>", c.render(format)))
+                    .collect();
+                lines.push(format!("  <tr>{}</tr>", cells.join("")));
+            }
+            lines.push("</table>".into());
+            lines.push(format!(
+                "<p><strong>Total: {total_millis:.2}ms</strong></p>"
+            ));
+        }
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// A standalone SVG with one bar per day, split into part 1 and part 2.
+#[allow(clippy::cast_precision_loss)]
+fn construct_svg(timings: &[Timings], total_millis: f64) -> String {
+    const BAR_X: f64 = 80.0;
+    const BAR_WIDTH: f64 = 480.0;
+    const ROW_HEIGHT: f64 = 24.0;
+    const TOP: f64 = 40.0;
+    const COLORS: [&str; 2] = ["#4c9be8", "#f0a030"];
+
+    let part_nanos = |t: &Timings| t.stats.clone().map(|s| s.map_or(0_f64, |s| s.nanos));
+    let max_nanos = timings
+        .iter()
+        .map(|t| part_nanos(t).iter().sum::<f64>())
+        .fold(0_f64, f64::max);
+    let scale = if max_nanos > 0_f64 {
+        BAR_WIDTH / max_nanos
+    } else {
+        0_f64
+    };
+
+    let height = TOP + ROW_HEIGHT * timings.len() as f64 + 40.0;
+    let width = BAR_X + BAR_WIDTH + 120.0;
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+    let mut lines = vec![
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">"
+        ),
+        "  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>".into(),
+        format!(
+            "  <text x=\"10\" y=\"24\" font-size=\"16\" font-weight=\"bold\">Benchmarks (total: {total_millis:.2}ms)</text>"
+        ),
     ];
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
+    for (i, timing) in timings.iter().enumerate() {
+        let y = TOP + ROW_HEIGHT * i as f64;
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "  <text x=\"10\" y=\"{}\">Day {}</text>",
+            y + 14.0,
+            timing.day
+        ));
+
+        let mut x = BAR_X;
+        for (part, nanos) in part_nanos(timing).iter().enumerate() {
+            let w = nanos * scale;
+            lines.push(format!(
+                "  <rect x=\"{x:.1}\" y=\"{y}\" width=\"{w:.1}\" height=\"18\" fill=\"{}\"><title>Part {}: {}</title></rect>",
+                COLORS[part],
+                part + 1,
+                format_nanos(*nanos)
+            ));
+            x += w;
+        }
+
+        lines.push(format!(
+            "  <text x=\"{:.1}\" y=\"{}\">{}</text>",
+            x + 6.0,
+            y + 14.0,
+            format_nanos(timing.total_nanos)
         ));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    let legend_y = TOP + ROW_HEIGHT * timings.len() as f64 + 16.0;
+    for (part, color) in COLORS.iter().enumerate() {
+        let x = BAR_X + 100.0 * part as f64;
+        lines.push(format!(
+            "  <rect x=\"{x}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{color}\"/>",
+            legend_y - 10.0
+        ));
+        lines.push(format!(
+            "  <text x=\"{}\" y=\"{legend_y}\">Part {}</text>",
+            x + 16.0,
+            part + 1
+        ));
+    }
 
+    lines.push("</svg>".into());
+    lines.push(String::new());
     lines.join("\n")
 }
 
+#[cfg(feature = "test_lib")]
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    update_content_with(s, &timings, total_millis, 0, &Config::default(), None)
+}
+
+fn update_content_with(
+    s: &mut String,
+    timings: &[Timings],
+    total_millis: f64,
+    year: u16,
+    config: &Config,
+    metadata: Option<&Metadata>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, year, config, metadata);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(timings: Vec<Timings>, total_millis: f64, config: &Config) -> Result<(), Error> {
+    let year = aoc_cli::get_year().unwrap_or_else(current_year);
+    let metadata = config.metadata.then(Metadata::collect);
+
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content_with(
+        &mut readme,
+        &timings,
+        total_millis,
+        year,
+        config,
+        metadata.as_ref(),
+    )?;
     fs::write(path, &readme)?;
+
+    if let Some(svg) = &config.svg {
+        fs::write(svg, construct_svg(&timings, total_millis))?;
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{
+        chart_bar, construct_svg, parse_columns, update_content, update_content_with, Column,
+        Config, Format, Metadata, Stats, Timings, MARKER,
+    };
     use crate::day;
+    use crate::template::heap::HeapStats;
+
+    fn timings(day: crate::Day, part_1: &str, part_2: &str, total_nanos: f64) -> Timings {
+        Timings {
+            day,
+            part_1: Some(part_1.into()),
+            part_2: Some(part_2.into()),
+            parse: None,
+            stats: [None, None],
+            heap: [None, None],
+            total_nanos,
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
-            Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
-            },
-            Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
-            },
-            Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
-            },
+            timings(day!(1), "10ms", "20ms", 3e+10),
+            timings(day!(2), "30ms", "40ms", 7e+10),
+            timings(day!(4), "40ms", "50ms", 9e+10),
         ]
     }
 
+    fn stats(time: &str, nanos: f64, stddev_nanos: f64, samples: u64) -> Option<Stats> {
+        Some(Stats {
+            time: time.into(),
+            nanos,
+            stddev_nanos,
+            samples,
+            raw_nanos: vec![],
+        })
+    }
+
+    fn get_mock_stats() -> Vec<Timings> {
+        let mut one = timings(day!(1), "1.0ms", "3.0ms", 4e+6);
+        one.parse = stats("500.0µs", 5e+5, 1e+4, 100);
+        one.stats = [
+            stats("1.0ms", 1e+6, 1e+5, 1000),
+            stats("3.0ms", 3e+6, 2e+5, 300),
+        ];
+
+        let mut two = timings(day!(2), "timed out", "1.0ms", 1e+6);
+        two.part_1 = Some("timed out".into());
+        two.stats = [None, stats("1.0ms", 1e+6, 0_f64, 1000)];
+
+        vec![one, two]
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
@@ -183,4 +670,120 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!(
+            parse_columns("parse,share,chart"),
+            Ok(vec![Column::Parse, Column::Share, Column::Chart])
+        );
+        assert!(parse_columns("parse,foo").is_err());
+        assert_eq!(parse_columns("all"), Ok(Column::ALL.to_vec()));
+    }
+
+    #[test]
+    fn draws_chart_bars() {
+        assert_eq!(chart_bar(1.0, 4), "████");
+        assert_eq!(chart_bar(0.5, 4), "██");
+        assert_eq!(chart_bar(0.3, 4), "█▎");
+        assert_eq!(chart_bar(0.0, 4), "▏");
+    }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let config = Config {
+            columns: vec![
+                Column::Links,
+                Column::Parse,
+                Column::Stddev,
+                Column::Samples,
+                Column::Share,
+                Column::Chart,
+            ],
+            ..Config::default()
+        };
+        let metadata = Metadata {
+            machine: "CPU (linux/x86_64)".into(),
+            rustc: "rustc 1.0.0".into(),
+            commit: "abc1234".into(),
+        };
+        update_content_with(
+            &mut s,
+            &get_mock_stats(),
+            5.0,
+            2023,
+            &config,
+            Some(&metadata),
+        )
+        .unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "_CPU (linux/x86_64) · rustc 1.0.0 · commit abc1234_",
+            "",
+            "| Day | Puzzle | Parse | Part 1 | Part 2 | Samples | Share | Chart |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | [Puzzle](https://adventofcode.com/2023/day/1) | `500.0µs ± 10.0µs` | `1.0ms ± 100.0µs` | `3.0ms ± 200.0µs` | 1000 / 300 | 80.0% | ████████████████████ |",
+            "| [Day 2](./src/bin/02.rs) | [Puzzle](https://adventofcode.com/2023/day/2) | `-` | `timed out` | `1.0ms ± 0.0ns` | - / 1000 | 20.0% | █████ |",
+            "",
+            "**Total: 5.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_heap_columns() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let config = Config {
+            columns: vec![Column::Heap],
+            ..Config::default()
+        };
+        let mut timings = get_mock_stats();
+        timings[0].heap = [
+            Some(HeapStats {
+                peak_bytes: 2048,
+                allocations: 3,
+                allocated_bytes: 4096,
+            }),
+            None,
+        ];
+        update_content_with(&mut s, &timings[..1], 4.0, 2023, &config, None).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak heap | Allocations |"));
+        assert!(s.contains("| `1.0ms` | `3.0ms` | 2.0 KiB / - | 3 (4.0 KiB) / - |"));
+    }
+
+    #[test]
+    fn format_benchmarks_as_html() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let config = Config {
+            format: Format::Html,
+            ..Config::default()
+        };
+        update_content_with(&mut s, &get_mock_stats()[..1], 4.0, 2023, &config, None).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "<h2>Benchmarks</h2>",
+            "<table>",
+            "  <tr><th>Day</th><th>Part 1</th><th>Part 2</th></tr>",
+            "  <tr><td><a href=\"./src/bin/01.rs\">Day 1</a></td><td><code>1.0ms</code></td><td><code>3.0ms</code></td></tr>",
+            "</table>",
+            "<p><strong>Total: 4.00ms</strong></p>",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn draws_svg_chart() {
+        let svg = construct_svg(&get_mock_stats(), 5.0);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches(">Day ").count(), 2);
+        assert!(svg.contains("<title>Part 2: 3.0ms</title>"));
+    }
 }
@@ -0,0 +1,109 @@
+/// Module that updates the readme with the locally recorded stars.
+/// Writes the same table as the `advent-readme-stars` workflow, so both can be used interchangeably.
+use std::fs;
+
+use crate::template::progress::DayProgress;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::Day;
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+fn construct_table(year: u16, progress: &[(Day, DayProgress)]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, progress) in progress.iter().filter(|(_, p)| p.stars() > 0) {
+        let star = |part: u8| {
+            if progress.part(part).answer.is_some() {
+                "⭐"
+            } else {
+                " "
+            }
+        };
+        let day = day.into_inner();
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{year}/day/{day}) | {} | {} |",
+            star(1),
+            star(2),
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: u16, progress: &[(Day, DayProgress)]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table(year, progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: u16, progress: &[(Day, DayProgress)]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::day;
+    use crate::template::progress::DayProgress;
+
+    fn get_mock_progress() -> Vec<(crate::Day, DayProgress)> {
+        let mut one = DayProgress::default();
+        one.part_mut(1).answer = Some("1".into());
+        one.part_mut(2).answer = Some("2".into());
+
+        let mut two = DayProgress::default();
+        two.part_mut(1).answer = Some("3".into());
+
+        let mut four = DayProgress::default();
+        four.part_mut(2).answer = Some("4".into());
+
+        vec![
+            (day!(1), one),
+            (day!(2), two),
+            (day!(3), DayProgress::default()),
+            (day!(4), four),
+        ]
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "| [Day 4](https://adventofcode.com/2023/day/4) |   | ⭐ |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        update_content(&mut s, 2023, &get_mock_progress()).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## 2023 Results").count(), 1);
+    }
+}
@@ -0,0 +1,406 @@
+/// Renders the markdown puzzle descriptions of [`crate::template::puzzle`] in the terminal.
+///
+/// Headings, emphasis, inline code and code blocks are styled with ANSI codes, and text is wrapped to the terminal.
+/// Files written by aoc-cli render too, their backslash escapes are removed. Blocks that were added since a previous
+/// version of the description are marked in the margin.
+use std::fs::File;
+use std::io::{stdout, IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::{env, io};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_CYAN: &str = "\x1b[36m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+const ADDED_MARKER: &str = "\x1b[32m┃\x1b[0m";
+
+/// Lines are not wrapped wider than this, even on wide terminals.
+const MAX_WIDTH: usize = 100;
+const DEFAULT_WIDTH: usize = 80;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    heading: bool,
+    em: bool,
+    code: bool,
+    link: bool,
+}
+
+impl Style {
+    fn prefix(self) -> String {
+        let mut prefix = String::new();
+        if self.heading {
+            prefix.push_str(ANSI_GREEN);
+        }
+        if self.em || self.heading {
+            prefix.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            prefix.push_str(ANSI_CYAN);
+        }
+        if self.link {
+            prefix.push_str(ANSI_UNDERLINE);
+        }
+        prefix
+    }
+}
+
+/// A word, which may switch styles, e.g. `` `12`, ``.
+#[derive(Default)]
+struct Word(Vec<(Style, String)>);
+
+impl Word {
+    fn push(&mut self, style: Style, c: char) {
+        match self.0.last_mut() {
+            Some((last, text)) if *last == style => text.push(c),
+            _ => self.0.push((style, c.to_string())),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.0.iter().map(|(_, text)| text.chars().count()).sum()
+    }
+
+    fn render(&self) -> String {
+        self.0
+            .iter()
+            .map(|(style, text)| {
+                if *style == Style::default() {
+                    text.clone()
+                } else {
+                    format!("{}{text}{ANSI_RESET}", style.prefix())
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits markdown text into styled words.
+fn parse_inline(text: &str, base: Style) -> Vec<Word> {
+    let mut words = vec![];
+    let mut word = Word::default();
+    let mut style = base;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if !style.code => {
+                if let Some(escaped) = chars.next() {
+                    word.push(style, escaped);
+                }
+            }
+            '`' => style.code = !style.code,
+            '*' if !style.code => style.em = !style.em,
+            '[' if !style.code && text.contains("](") => style.link = true,
+            ']' if style.link && chars.peek() == Some(&'(') => {
+                style.link = false;
+                // the url is not shown, the text is what matters.
+                for c in chars.by_ref() {
+                    if c == ')' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if !word.0.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(style, c),
+        }
+    }
+
+    if !word.0.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Wraps words to `width`, indenting every line by `indent` and the first line by `first_indent` instead.
+fn wrap(words: &[Word], width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_width = first_indent.chars().count();
+    let mut empty = true;
+
+    for word in words {
+        if !empty && line_width + 1 + word.width() > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            empty = true;
+        }
+        if !empty {
+            line.push(' ');
+            line_width += 1;
+        }
+        line.push_str(&word.render());
+        line_width += word.width();
+        empty = false;
+    }
+
+    if !empty {
+        lines.push(line);
+    }
+    lines
+}
+
+#[derive(PartialEq, Eq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    ListItem(String),
+    Code(Vec<String>),
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut code: Option<Vec<String>> = None;
+
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match code.take() {
+                Some(lines) => blocks.push(Block::Code(lines)),
+                None => {
+                    flush(&mut paragraph, &mut blocks);
+                    code = Some(vec![]);
+                }
+            }
+        } else if let Some(lines) = code.as_mut() {
+            lines.push(line.to_string());
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else if line.starts_with('#') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(
+                line.trim_start_matches('#').trim().to_string(),
+            ));
+        } else if let Some(item) = line.strip_prefix("- ").or(line.strip_prefix("* ")) {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::ListItem(item.trim().to_string()));
+        } else if let Some(Block::ListItem(item)) = blocks
+            .last_mut()
+            .filter(|_| paragraph.is_empty() && line.starts_with("  "))
+        {
+            item.push(' ');
+            item.push_str(line.trim());
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    if let Some(lines) = code {
+        blocks.push(Block::Code(lines));
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// Which blocks of `new` are not in `old`, using their longest common subsequence.
+fn find_added(old: &[Block], new: &[Block]) -> Vec<bool> {
+    let mut lengths = vec![vec![0_usize; old.len() + 1]; new.len() + 1];
+    for i in (0..new.len()).rev() {
+        for j in (0..old.len()).rev() {
+            lengths[i][j] = if new[i] == old[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut added = vec![true; new.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < new.len() && j < old.len() {
+        if new[i] == old[j] {
+            added[i] = false;
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    added
+}
+
+fn render_block(block: &Block, width: usize) -> Vec<String> {
+    match block {
+        Block::Heading(text) => {
+            let style = Style {
+                heading: true,
+                ..Style::default()
+            };
+            wrap(&parse_inline(text, style), width, "", "")
+        }
+        Block::Paragraph(text) => wrap(&parse_inline(text, Style::default()), width, "", ""),
+        Block::ListItem(text) => wrap(&parse_inline(text, Style::default()), width, "  • ", "    "),
+        Block::Code(lines) => lines
+            .iter()
+            .map(|line| format!("  {ANSI_CYAN}{line}{ANSI_RESET}"))
+            .collect(),
+    }
+}
+
+/// Renders a markdown puzzle description for a terminal that is `width` columns wide. Blocks that are not in
+/// `previous` are marked as added.
+#[must_use]
+pub fn render(markdown: &str, previous: Option<&str>, width: usize) -> String {
+    let width = width.clamp(20, MAX_WIDTH);
+    let blocks = parse_blocks(markdown);
+    let added = match previous {
+        Some(previous) => find_added(&parse_blocks(previous), &blocks),
+        None => vec![false; blocks.len()],
+    };
+
+    let mut lines = vec![];
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0
+            && !matches!(
+                (&blocks[i - 1], block),
+                (Block::ListItem(_), Block::ListItem(_))
+            )
+        {
+            let separator = if added[i - 1] && added[i] {
+                ADDED_MARKER
+            } else {
+                ""
+            };
+            lines.push(separator.to_string());
+        }
+
+        if added[i] {
+            let rendered = render_block(block, width - 2);
+            lines.extend(
+                rendered
+                    .into_iter()
+                    .map(|line| format!("{ADDED_MARKER} {line}")),
+            );
+        } else {
+            lines.extend(render_block(block, width));
+        }
+    }
+
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+/// The width of the terminal, from `COLUMNS` or `stty`.
+#[must_use]
+pub fn terminal_width() -> usize {
+    if let Some(columns) = env::var("COLUMNS").ok().and_then(|s| s.parse().ok()) {
+        return columns;
+    }
+
+    File::open("/dev/tty")
+        .ok()
+        .and_then(|tty| {
+            Command::new("stty")
+                .arg("size")
+                .stdin(tty)
+                .stderr(Stdio::null())
+                .output()
+                .ok()
+        })
+        .and_then(|output| {
+            let size = String::from_utf8_lossy(&output.stdout).into_owned();
+            size.split_whitespace().nth(1)?.parse().ok()
+        })
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Shows text in `$PAGER`, or `less`, if stdout is a terminal. Prints it directly otherwise.
+pub fn page(text: &str) -> io::Result<()> {
+    if !stdout().is_terminal() {
+        return stdout().write_all(text.as_bytes());
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -RFX".into());
+    let mut args = pager.split_whitespace();
+    let Some(program) = args.next() else {
+        return stdout().write_all(text.as_bytes());
+    };
+
+    match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager may quit before everything is written.
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            child.wait()?;
+            Ok(())
+        }
+        Err(_) => stdout().write_all(text.as_bytes()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+
+    const DESCRIPTION: &str = "## --- Day 1: Trebuchet?! ---
+
+The values are `12`, `38` and *`15`*. Adding these produces *`142`*, see the [about page](https://adventofcode.com/2023/about).
+
+```
+1abc2
+pqr3stu8vwx a1b2c3d4e5f
+```
+
+- first item
+- second item that is long enough to wrap
+";
+
+    #[test]
+    fn renders_description() {
+        assert_eq!(
+            render(DESCRIPTION, None, 30),
+            "\x1b[32m\x1b[1m---\x1b[0m \x1b[32m\x1b[1mDay\x1b[0m \x1b[32m\x1b[1m1:\x1b[0m \x1b[32m\x1b[1mTrebuchet?!\x1b[0m \x1b[32m\x1b[1m---\x1b[0m
+\nThe values are \x1b[36m12\x1b[0m, \x1b[36m38\x1b[0m and \x1b[1m\x1b[36m15\x1b[0m.
+Adding these produces \x1b[1m\x1b[36m142\x1b[0m, see
+the \x1b[4mabout\x1b[0m \x1b[4mpage\x1b[0m.
+\n  \x1b[36m1abc2\x1b[0m
+  \x1b[36mpqr3stu8vwx a1b2c3d4e5f\x1b[0m
+\n  • first item
+  • second item that is long
+    enough to wrap
+"
+        );
+    }
+
+    #[test]
+    fn removes_escapes() {
+        assert_eq!(render("\\--- Day 1 \\---", None, 80), "--- Day 1 ---\n");
+    }
+
+    #[test]
+    fn marks_added_blocks() {
+        let previous = "# Part One\n\nFor example:\n";
+        let current = "# Part One\n\nFor example:\n\n# Part Two\n\nFor example:\n";
+
+        assert_eq!(
+            render(current, Some(previous), 80),
+            "\x1b[32m\x1b[1mPart\x1b[0m \x1b[32m\x1b[1mOne\x1b[0m
+\nFor example:
+\n\x1b[32m┃\x1b[0m \x1b[32m\x1b[1mPart\x1b[0m \x1b[32m\x1b[1mTwo\x1b[0m
+\x1b[32m┃\x1b[0m
+\x1b[32m┃\x1b[0m For example:
+"
+        );
+    }
+}
@@ -1,6 +1,10 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::budget::{self, Budget, Supervision};
+use crate::template::cli::{self, parse_part, SOLUTION};
+use crate::template::heap::{self, HeapStats};
+use crate::template::input::Input;
+use crate::template::{aoc_cli, input_sets, progress, puzzle, ANSI_ITALIC, ANSI_RESET};
+use crate::{configured_year, Day};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
@@ -9,16 +13,337 @@ use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The arguments of a day binary. `solution!` parses them once and passes them to the runner.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub time: bool,
+    pub trace: bool,
+    pub submit: Option<u8>,
+    pub input: Input,
+    /// Only run this part. Set for the child processes of [`budget::supervise`].
+    pub part: Option<u8>,
+    pub budget: Budget,
+    /// Run the parts repeatedly for this long instead of once, e.g. to sample them with `cargo profile`.
+    pub duration: Option<Duration>,
+    pub raw_samples: bool,
+    pub heap_stats: bool,
+    pub variant: Option<String>,
+    pub all_variants: bool,
+}
+
+impl Options {
+    /// Parses the arguments of a day binary, without the executable.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, cli::Error> {
+        let matches = cli::parse(&SOLUTION, args)?;
+
+        let input = Input::from_matches(&matches)?;
+
+        if let Some(flag) = input.flag().filter(|_| matches.contains("--submit")) {
+            return Err(cli::Error::Conflict("--submit", flag));
+        }
+        if matches.contains("--time") && matches.contains("--duration") {
+            return Err(cli::Error::Conflict("--time", "--duration"));
+        }
+        // tracing is compiled out of optimized builds, see `trace!`.
+        if matches.contains("--trace") && !cfg!(debug_assertions) {
+            return Err(cli::Error::Conflict("--trace", "--release"));
+        }
+
+        Ok(Self {
+            time: matches.contains("--time"),
+            trace: matches.contains("--trace"),
+            submit: matches.value_from_fn("--submit", parse_part)?,
+            input,
+            part: matches.value_from_fn("--part", parse_part)?,
+            budget: Budget::new(
+                matches.value_from_fn("--timeout", cli::parse_secs)?,
+                matches.value_from_str("--memory")?,
+            ),
+            duration: matches.value_from_fn("--duration", cli::parse_secs)?,
+            raw_samples: matches.contains("--raw-samples"),
+            heap_stats: matches.contains("--heap-stats"),
+            variant: matches.value("--variant").map(String::from),
+            all_variants: matches.contains("--all-variants"),
+        })
+    }
+
+    /// Parses the arguments of the current process, exiting on invalid arguments.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().skip(1).collect();
+
+        if args.iter().any(|x| x == "--help" || x == "-h") {
+            print!("{}", cli::command_help(&SOLUTION));
+            process::exit(0);
+        }
+
+        Self::parse(args).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        })
+    }
+
+    /// The arguments that pass these options on to a day binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        let switches = [
+            ("--time", self.time),
+            ("--trace", self.trace),
+            ("--raw-samples", self.raw_samples),
+            ("--heap-stats", self.heap_stats),
+            ("--all-variants", self.all_variants),
+        ];
+        args.extend(
+            switches
+                .iter()
+                .filter(|(_, enabled)| *enabled)
+                .map(|(flag, _)| (*flag).to_string()),
+        );
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+        if let Some(part) = self.part {
+            args.extend(["--part".into(), part.to_string()]);
+        }
+        if let Some(variant) = &self.variant {
+            args.extend(["--variant".into(), variant.clone()]);
+        }
+        if let Some(duration) = self.duration {
+            args.extend(["--duration".into(), duration.as_secs_f64().to_string()]);
+        }
+        args.extend(self.input.to_args());
+        args.extend(self.budget.to_args());
+        args
+    }
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    options: &Options,
+) {
+    if !matches!(budget::supervise(part, options), Supervision::Run) {
+        return;
+    }
+
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, timing) = run_timed(func, input, options, |result| {
+        print_result(result, &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let heap_str = timing
+        .heap
+        .map_or_else(String::new, |heap| format!(" [{heap}]"));
+    print_result(
+        &result,
+        &part_str,
+        &format!("{}{heap_str}", format_duration(&timing)),
+    );
+    print_raw_samples(&part_str, &timing, options);
+    print_heap_stats(&part_str, &timing, options);
+
+    // only optimized, benched timings of the puzzle input are worth keeping track of.
+    if result.is_some()
+        && options.time
+        && timing.samples > 1
+        && !cfg!(debug_assertions)
+        && options.input.is_puzzle()
+    {
+        if let Err(e) = progress::record_time(day, part, timing.mean) {
+            eprintln!("Failed to record timing: {e}");
+        }
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        let answer = result.to_string();
+
+        if let Input::Set(name) = &options.input {
+            println!("{}", input_sets::check(name, day, part, &answer).line(part));
+        }
+
+        match submit_result(result, day, part, options) {
+            Some(Ok(output)) if aoc_cli::is_accepted(&output) => {
+                if let Err(e) = progress::record_answer(day, part, &answer) {
+                    eprintln!("Failed to record answer: {e}");
+                }
+                if part == 1 {
+                    refresh_description(day);
+                }
+            }
+            Some(Err(e)) => eprintln!("Failed to submit: {e}"),
+            _ => {}
+        }
+    }
+}
+
+/// Downloads the description again after part one was accepted, which unlocks part two.
+fn refresh_description(day: Day) {
+    match puzzle::download(configured_year(), day, true) {
+        Ok(_) => println!("🎄 Part two is unlocked. Run \"cargo read {day} --part 2\" to read it."),
+        Err(e) => eprintln!("Failed to download part two: {e}"),
+    }
+}
+
+/// A named implementation of a part, see the `variants` argument of `solution!`.
+pub type Variant<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// Runs the variant of a part that was selected with `--variant <name>`, or the default one.
+/// With `--all-variants`, every variant runs and their answers are compared instead.
+/// Exits if `--variant` names a variant that neither part has, listing the `names` of all variants.
+/// A variant of only one part is fine, the other part then reports that it has no such variant.
+pub fn check_variant(names: &[&str], options: &Options) {
+    let Some(name) = &options.variant else {
+        return;
+    };
+
+    if !names.contains(&name.as_str()) {
+        // both parts usually share the names of their variants.
+        let mut available: Vec<&str> = vec![];
+        for name in names {
+            if !available.contains(name) {
+                available.push(name);
+            }
+        }
+        eprintln!("No variant \"{name}\", available: {}", available.join(", "));
+        process::exit(1);
+    }
+}
+
+pub fn run_variants<T: Display>(
+    variants: &[Variant<T>],
+    input: &str,
+    day: Day,
+    part: u8,
+    options: &Options,
+) {
+    if options.all_variants {
+        compare_variants(variants, input, part, options);
+        return;
+    }
+
+    let Some(name) = &options.variant else {
+        run_part(variants[0].1, input, day, part, options);
+        return;
+    };
+
+    match variants.iter().find(|(n, _)| n == name) {
+        Some((_, func)) => run_part(*func, input, day, part, options),
+        None => {
+            let names: Vec<_> = variants.iter().map(|(n, _)| *n).collect();
+            println!(
+                "Part {part}: ✖ no variant \"{name}\", available: {}",
+                names.join(", ")
+            );
+        }
+    }
+}
+
+fn compare_variants<T: Display>(variants: &[Variant<T>], input: &str, part: u8, options: &Options) {
+    let mut results = vec![];
+
+    for (name, func) in variants {
+        let label = format!("Part {part} [{name}]");
+        let (result, timing) = run_timed(func, input, options, |result| {
+            print_result(result, &label, "");
+        });
+        print_result(&result, &label, &format_duration(&timing));
+        results.push((*name, result.map(|r| r.to_string()), timing));
+    }
+
+    let (_, expected, _) = &results[0];
+    let disagreeing: Vec<_> = results
+        .iter()
+        .filter(|(_, answer, _)| answer != expected)
+        .map(|(name, _, _)| *name)
+        .collect();
+
+    if !disagreeing.is_empty() {
+        eprintln!(
+            "Part {part}: {ANSI_BOLD}variants disagree{ANSI_RESET}, {} differ from default.",
+            disagreeing.join(", ")
+        );
+        process::exit(1);
+    }
+
+    println!(
+        "Part {part}: {ANSI_BOLD}all {} variants agree{ANSI_RESET}",
+        results.len()
+    );
+
+    // side by side comparison for benched runs.
+    if results.len() < 2 || results.iter().any(|(_, _, timing)| timing.samples == 1) {
+        return;
+    }
+
+    results.sort_by_key(|(_, _, timing)| timing.mean);
+    let fastest = results[0].2.mean.as_secs_f64().max(f64::EPSILON);
+    let width = results
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    for (i, (name, _, timing)) in results.iter().enumerate() {
+        let relative = if i == 0 {
+            "fastest".to_string()
+        } else {
+            format!("{:.2}x", timing.mean.as_secs_f64() / fastest)
+        };
+        println!(
+            "  {name:<width$}  {:>10}  {ANSI_ITALIC}{relative}{ANSI_RESET}",
+            format!("{:.1?}", timing.mean)
+        );
+    }
+}
+
+/// Times the parsing step of a solution, see the `parse` argument of `solution!`.
+/// The parsed value is discarded, parts still parse their input themselves.
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, options: &Options) {
+    // when parts run in separate processes, only time parsing in the parent.
+    if options.part.is_some() {
+        return;
+    }
+
+    let (_, timing) = run_timed(func, input, options, |_| print!("Parse: "));
+    println!("\rParse:{}", format_duration(&timing));
+    print_raw_samples("Parse", &timing, options);
+}
+
+/// Output lines with raw samples start with `<label> RAW_SAMPLES:`, followed by nanoseconds.
+pub const RAW_SAMPLES: &str = "raw samples";
+
+/// With `--raw-samples`, prints every sample of a benched run, so that `all` can export them.
+fn print_raw_samples(label: &str, timing: &Timing, options: &Options) {
+    if timing.raw.is_empty() || !options.raw_samples {
+        return;
+    }
+
+    let nanos: Vec<String> = timing
+        .raw
+        .iter()
+        .map(|d| d.as_nanos().to_string())
+        .collect();
+    println!("{label} {RAW_SAMPLES}: {}", nanos.join(" "));
+}
+
+/// Output lines with heap stats start with `<label> HEAP_STATS:`, followed by peak bytes, allocations and allocated bytes.
+pub const HEAP_STATS: &str = "heap stats";
+
+/// With `--heap-stats`, prints the heap usage of a part, so that `all` can report it.
+fn print_heap_stats(label: &str, timing: &Timing, options: &Options) {
+    let Some(heap) = timing.heap else {
+        return;
+    };
+
+    if options.heap_stats {
+        println!(
+            "{label} {HEAP_STATS}: {} {} {}",
+            heap.peak_bytes, heap.allocations, heap.allocated_bytes
+        );
     }
 }
 
@@ -28,36 +353,87 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &Options,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Timing) {
+    let cloned = input.clone();
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, heap) = heap::measure(|| func(cloned));
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let mut timing = match options.duration {
+        Some(duration) => repeat(func, input, base_time, duration),
+        None if options.time => bench(func, input, &base_time),
+        None => Timing {
+            mean: base_time,
+            stddev: Duration::ZERO,
+            samples: 1,
+            raw: vec![],
+            heap: None,
+        },
     };
 
-    (result, run.0, run.1)
+    // heap usage is only measured for the first run, benched runs would add up.
+    timing.heap = heap;
+
+    (result, timing)
+}
+
+/// The execution time of a part, averaged over `samples` runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub samples: u128,
+    /// Every sample of a benched run, empty if the part only ran once.
+    pub raw: Vec<Duration>,
+    /// Only measured with the `count_allocations` feature.
+    pub heap: Option<HeapStats>,
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Runs a part again until `duration` has passed since its first run started.
+fn repeat<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: Duration,
+    duration: Duration,
+) -> Timing {
+    print!(" > {ANSI_ITALIC}repeating{ANSI_RESET}");
+    let _ = stdout().flush();
+
+    let mut timers = vec![base_time];
+    let mut elapsed = base_time;
+
+    while elapsed < duration {
+        let cloned = input.clone();
+        let timer = Instant::now();
+        func(cloned);
+        timers.push(timer.elapsed());
+        elapsed += timers[timers.len() - 1];
+    }
+
+    let average = average_duration(&timers);
+
+    #[allow(clippy::cast_possible_truncation)]
+    Timing {
+        mean: Duration::from_nanos(average as u64),
+        stddev: Duration::from_nanos(stddev_duration(&timers, average) as u64),
+        samples: timers.len() as u128,
+        raw: vec![],
+        heap: None,
+    }
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Timing {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,11 +445,16 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let average = average_duration(&timers);
+
+    #[allow(clippy::cast_possible_truncation)]
+    Timing {
+        mean: Duration::from_nanos(average as u64),
+        stddev: Duration::from_nanos(stddev_duration(&timers, average) as u64),
+        samples: bench_iterations,
+        raw: timers,
+        heap: None,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,11 +465,28 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+#[allow(clippy::cast_precision_loss)]
+fn stddev_duration(numbers: &[Duration], average: u128) -> f64 {
+    let variance = numbers
+        .iter()
+        .map(|d| (d.as_nanos() as f64 - average as f64).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    variance.sqrt()
+}
+
+fn format_duration(timing: &Timing) -> String {
+    let Timing {
+        mean,
+        stddev,
+        samples,
+        ..
+    } = timing;
+
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({mean:.1?} ± {stddev:.1?} @ {samples} samples)")
     }
 }
 
@@ -97,7 +495,9 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
+            // the alternate form displays the picture of an `Answer`.
+            let result = format!("{result:#}");
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -127,41 +527,118 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
+/// Try to submit one part of the solution if:
+///  1. `--submit` selected this part.
 ///  2. aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
+    options: &Options,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
+    if options.submit != Some(part) {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    println!("Submitting result via aoc-cli...");
+    Some(aoc_cli::submit(day, part, &result.to_string()))
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+/* -------------------------------------------------------------------------- */
 
-    if part_submit != part {
-        return None;
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Options;
+    use crate::template::budget::Budget;
+    use crate::template::cli;
+    use crate::template::input::Input;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn parses_options() {
+        let options = Options::parse(args("--time --submit 2 --timeout 2.5 --memory 512")).unwrap();
+        assert!(options.time);
+        assert_eq!(options.submit, Some(2));
+        assert_eq!(options.budget.timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(options.budget.memory, Some(512));
+        assert!(Options::parse(args("--time"))
+            .unwrap()
+            .budget
+            .is_unlimited());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn rejects_invalid_options() {
+        assert_eq!(
+            Options::parse(args("--timeout")).unwrap_err(),
+            cli::Error::MissingValue("--timeout")
+        );
+        for timeout in ["-1", "NaN"] {
+            assert_eq!(
+                Options::parse(args(&format!("--timeout {timeout}"))).unwrap_err(),
+                cli::Error::InvalidValue {
+                    name: "--timeout".into(),
+                    value: timeout.into(),
+                    reason: "expecting a positive number of seconds".into(),
+                }
+            );
+        }
+        assert!(matches!(
+            Options::parse(args("--submit 3")).unwrap_err(),
+            cli::Error::InvalidValue { .. }
+        ));
+        assert_eq!(
+            Options::parse(args("--bogus")).unwrap_err(),
+            cli::Error::UnknownArgument("--bogus".into())
+        );
+    }
+
+    #[test]
+    fn parses_inputs() {
+        assert_eq!(Options::parse(args("")).unwrap().input, Input::Puzzle);
+        assert_eq!(
+            Options::parse(args("--example 2")).unwrap().input,
+            Input::Example(Some(2))
+        );
+        assert_eq!(
+            Options::parse(args("--input - --time")).unwrap().input,
+            Input::Stdin
+        );
+        assert_eq!(
+            Options::parse(args("--input a.txt --example")).unwrap_err(),
+            cli::Error::Conflict("--input", "--example")
+        );
+        assert_eq!(
+            Options::parse(args("--input-set bob --example")).unwrap_err(),
+            cli::Error::Conflict("--example", "--input-set")
+        );
+        assert_eq!(
+            Options::parse(args("--example --submit 1")).unwrap_err(),
+            cli::Error::Conflict("--submit", "--example")
+        );
+    }
+
+    #[test]
+    fn round_trips_options() {
+        let options = Options {
+            time: true,
+            part: Some(2),
+            budget: Budget::new(Some(Duration::from_secs(10)), Some(64)),
+            raw_samples: true,
+            variant: Some("scan".into()),
+            input: Input::Example(Some(2)),
+            ..Options::default()
+        };
+        assert_eq!(Options::parse(options.to_args()).unwrap(), options);
+    }
 }
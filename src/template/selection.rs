@@ -0,0 +1,68 @@
+/// Resolves the keywords of day arguments on the command line, e.g. `cargo all unsolved` or `cargo read today`.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::template::progress;
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::{all_days, day_count, Day, DaySet, DaySetFromStrError};
+
+/// An error which is returned when selecting `today` outside of advent.
+#[derive(Debug)]
+pub struct NoPuzzleTodayError;
+
+impl Error for NoPuzzleTodayError {}
+
+impl Display for NoPuzzleTodayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "there is no puzzle today, puzzles unlock at midnight EST on the first {} days of December",
+            day_count()
+        )
+    }
+}
+
+/// Parses a single day, which is either a day number or `today`.
+pub fn parse_day(s: &str) -> Result<Day, Box<dyn Error>> {
+    match s {
+        "today" => Day::today().ok_or_else(|| NoPuzzleTodayError.into()),
+        s => Ok(s.parse()?),
+    }
+}
+
+/// Parses a day selection such as `1-5,8,12..`, resolving the keywords:
+///  - `solved`: days with an accepted answer in their progress file.
+///  - `unsolved`: days that don't.
+///  - `scaffolded`: days that have a solution file in `src/bin`.
+///  - `today`: the current day of advent in EST, see [`Day::today`].
+pub fn parse_day_set(s: &str) -> Result<DaySet, Box<dyn Error>> {
+    if s.split(',').any(|item| item.trim() == "today") && Day::today().is_none() {
+        return Err(NoPuzzleTodayError.into());
+    }
+
+    let days: Result<DaySet, DaySetFromStrError> = DaySet::parse_with(s, resolve_keyword);
+    Ok(days?)
+}
+
+fn resolve_keyword(keyword: &str) -> Option<DaySet> {
+    match keyword {
+        "solved" => Some(solved_days()),
+        "unsolved" => Some(solved_days().complement()),
+        "scaffolded" => Some(scaffolded_days()),
+        "today" => Day::today().map(DaySet::from),
+        _ => None,
+    }
+}
+
+fn solved_days() -> DaySet {
+    all_days()
+        .filter(|day| progress::load(*day).stars() > 0)
+        .collect()
+}
+
+fn scaffolded_days() -> DaySet {
+    all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect()
+}
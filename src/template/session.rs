@@ -0,0 +1,197 @@
+/// Session cookies for adventofcode.com, stored per profile.
+///
+/// The `default` profile uses `~/.adventofcode.session`, the file aoc-cli reads by default. Named profiles are stored
+/// in `~/.adventofcode.<profile>.session`. `AOC_SESSION_PROFILE` selects the profile that commands use, and
+/// `ADVENT_OF_CODE_SESSION` overrides its cookie, as it does for aoc-cli.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use crate::template::http;
+
+pub const DEFAULT_PROFILE: &str = "default";
+pub const PROFILE_ENV: &str = "AOC_SESSION_PROFILE";
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+const FILE_PREFIX: &str = ".adventofcode";
+const FILE_SUFFIX: &str = ".session";
+
+/// The profile that commands use.
+#[must_use]
+pub fn active_profile() -> String {
+    env::var(PROFILE_ENV)
+        .ok()
+        .filter(|profile| !profile.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.into())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+#[must_use]
+pub fn get_path_for_session(profile: &str) -> Option<PathBuf> {
+    let file = if profile == DEFAULT_PROFILE {
+        format!("{FILE_PREFIX}{FILE_SUFFIX}")
+    } else {
+        format!("{FILE_PREFIX}.{profile}{FILE_SUFFIX}")
+    };
+    home_dir().map(|home| home.join(file))
+}
+
+/// Whether `ADVENT_OF_CODE_SESSION` overrides the stored cookie of `profile`.
+#[must_use]
+pub fn is_overridden(profile: &str) -> bool {
+    profile == active_profile() && env::var(SESSION_ENV).is_ok_and(|s| !s.trim().is_empty())
+}
+
+/// The session cookie of a profile, if one is set.
+#[must_use]
+pub fn load(profile: &str) -> Option<String> {
+    if is_overridden(profile) {
+        return env::var(SESSION_ENV).ok().map(|s| s.trim().to_string());
+    }
+
+    let contents = fs::read_to_string(get_path_for_session(profile)?).ok()?;
+    Some(contents.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+pub fn save(profile: &str, token: &str) -> io::Result<PathBuf> {
+    let path = get_path_for_session(profile)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))?;
+    fs::write(&path, format!("{token}\n"))?;
+
+    // the cookie grants access to the account, keep it private.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(path)
+}
+
+/// Removes the session cookie of a profile. Returns whether one was stored.
+pub fn clear(profile: &str) -> io::Result<bool> {
+    let Some(path) = get_path_for_session(profile) else {
+        return Ok(false);
+    };
+
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// The names of all profiles with a stored session cookie.
+#[must_use]
+pub fn list() -> Vec<String> {
+    let Some(entries) = home_dir().and_then(|home| fs::read_dir(home).ok()) else {
+        return vec![];
+    };
+
+    let mut profiles: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| profile_from_file_name(&name))
+        .collect();
+
+    profiles.sort();
+    profiles
+}
+
+fn profile_from_file_name(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    match rest {
+        "" => Some(DEFAULT_PROFILE.into()),
+        rest => rest
+            .strip_prefix('.')
+            .filter(|profile| !profile.is_empty())
+            .map(String::from),
+    }
+}
+
+/// An error which is returned when a session cookie is not a hex string.
+#[derive(Debug)]
+pub struct InvalidTokenError;
+
+impl Error for InvalidTokenError {}
+
+impl Display for InvalidTokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a session cookie is a hex string, copy the value of the \"session\" cookie of adventofcode.com")
+    }
+}
+
+/// Cleans up a pasted session cookie, which may include the cookie name.
+pub fn parse_token(s: &str) -> Result<String, InvalidTokenError> {
+    let token = s.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(InvalidTokenError);
+    }
+
+    Ok(token.to_string())
+}
+
+/// Shows the start and end of a session cookie, e.g. `5361…a1b2`.
+#[must_use]
+pub fn mask(token: &str) -> String {
+    if token.len() <= 12 {
+        return "*".repeat(token.len());
+    }
+
+    format!("{}…{}", &token[..4], &token[token.len() - 4..])
+}
+
+/// Whether adventofcode.com accepts a session cookie.
+pub fn validate(token: &str) -> Result<bool, http::Error> {
+    match http::get_with_session(http::Endpoint::Settings, "/settings", token) {
+        Ok(_) => Ok(true),
+        Err(http::Error::InvalidSession) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mask, parse_token, profile_from_file_name};
+
+    #[test]
+    fn parses_tokens() {
+        assert_eq!(
+            parse_token("  53616c7465645f5f\n").unwrap(),
+            "53616c7465645f5f"
+        );
+        assert_eq!(parse_token("session=53616c74").unwrap(), "53616c74");
+        assert!(parse_token("").is_err());
+        assert!(parse_token("not a cookie").is_err());
+    }
+
+    #[test]
+    fn masks_tokens() {
+        assert_eq!(mask("53616c7465645f5f0123456789abcdef"), "5361…cdef");
+        assert_eq!(mask("abc"), "***");
+    }
+
+    #[test]
+    fn reads_profiles_from_file_names() {
+        assert_eq!(
+            profile_from_file_name(".adventofcode.session").as_deref(),
+            Some("default")
+        );
+        assert_eq!(
+            profile_from_file_name(".adventofcode.work.session").as_deref(),
+            Some("work")
+        );
+        assert_eq!(profile_from_file_name(".adventofcode..session"), None);
+        assert_eq!(profile_from_file_name(".bashrc"), None);
+    }
+}
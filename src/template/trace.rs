@@ -0,0 +1,221 @@
+/// Debug output and frame recording for solutions.
+///
+/// Messages and frames are only emitted in debug builds, and never while benchmarking with `--time`.
+/// `debug!` messages are always shown otherwise, `trace!` messages and frames require `--trace`.
+/// Frames are written to `data/traces/{day}.txt` and can be stepped through with `cargo view <day>`.
+use std::env;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use crate::template::runner::Options;
+use crate::Day;
+
+/// Marks the start of a frame in a trace file, followed by the frame number and its label.
+pub const FRAME_MARKER: &str = "=== frame ";
+
+struct Flags {
+    trace: bool,
+    time: bool,
+}
+
+struct Recorder {
+    writer: BufWriter<File>,
+    frames: usize,
+}
+
+static FLAGS: OnceLock<Flags> = OnceLock::new();
+static RECORDER: OnceLock<Mutex<Recorder>> = OnceLock::new();
+
+fn flags() -> &'static Flags {
+    FLAGS.get_or_init(|| Flags {
+        trace: false,
+        time: false,
+    })
+}
+
+#[must_use]
+pub fn get_path_for_trace(day: Day) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("traces")
+        .join(format!("{day}.txt"))
+}
+
+/// Whether `debug!` messages should be printed.
+#[must_use]
+pub fn is_debug_enabled() -> bool {
+    !flags().time
+}
+
+/// Whether `trace!` messages and frames should be emitted.
+#[must_use]
+pub fn is_trace_enabled() -> bool {
+    flags().trace && !flags().time
+}
+
+/// Applies `--trace` and `--time` and sets up the trace file for `day`. Called by the `solution!` entry point.
+pub fn init(day: Day, options: &Options) {
+    let _ = FLAGS.set(Flags {
+        trace: options.trace,
+        time: options.time,
+    });
+
+    if !cfg!(debug_assertions) || !is_trace_enabled() {
+        return;
+    }
+
+    let path = get_path_for_trace(day);
+    let file = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| File::create(&path));
+
+    match file {
+        Ok(file) => {
+            let recorder = Recorder {
+                writer: BufWriter::new(file),
+                frames: 0,
+            };
+            let _ = RECORDER.set(Mutex::new(recorder));
+        }
+        Err(e) => eprintln!("Failed to create trace file: {e}"),
+    }
+}
+
+/// Appends a frame to the trace file. Does nothing if [`init`] did not open a trace file.
+pub fn record_frame(label: &str, body: &str) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+    let Ok(mut recorder) = recorder.lock() else {
+        return;
+    };
+
+    recorder.frames += 1;
+    let number = recorder.frames;
+    let result = writeln!(recorder.writer, "{FRAME_MARKER}{number}: {label}")
+        .and_then(|()| writeln!(recorder.writer, "{body}"))
+        .and_then(|()| recorder.writer.flush());
+
+    if let Err(e) = result {
+        eprintln!("Failed to write trace frame: {e}");
+    }
+}
+
+/// Formats rows of cells as text, one line per row, e.g. to record a grid snapshot.
+pub fn format_grid<R, C>(rows: impl IntoIterator<Item = R>) -> String
+where
+    R: IntoIterator<Item = C>,
+    C: Display,
+{
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|c| c.to_string()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A recorded frame, read back from a trace file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub label: String,
+    pub body: String,
+}
+
+/// Reads the frames of a trace file. Lines before the first frame marker are ignored.
+#[must_use]
+pub fn parse_frames(s: &str) -> Vec<Frame> {
+    let mut frames: Vec<(String, Vec<&str>)> = vec![];
+
+    for line in s.lines() {
+        if let Some(header) = line.strip_prefix(FRAME_MARKER) {
+            let label = header.split_once(": ").map_or("", |(_, label)| label);
+            frames.push((label.to_string(), vec![]));
+        } else if let Some((_, body)) = frames.last_mut() {
+            body.push(line);
+        }
+    }
+
+    frames
+        .into_iter()
+        .map(|(label, body)| Frame {
+            label,
+            body: body.join("\n"),
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints a message to stderr in debug builds, unless running with `--time`.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::template::trace::is_debug_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Prints a message to stderr in debug builds when running with `--trace`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) && $crate::template::trace::is_trace_enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Records a frame (anything that implements `Display`) in debug builds when running with `--trace`.
+/// The body is only evaluated if the frame is recorded.
+#[macro_export]
+macro_rules! trace_frame {
+    ($label:expr, $body:expr) => {
+        if cfg!(debug_assertions) && $crate::template::trace::is_trace_enabled() {
+            $crate::template::trace::record_frame(&$label.to_string(), &$body.to_string());
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_grid, parse_frames, Frame};
+
+    #[test]
+    fn parses_frames() {
+        let frames = parse_frames("=== frame 1: start\n#.\n.#\n=== frame 2: end: done\n\nx");
+        assert_eq!(
+            frames,
+            vec![
+                Frame {
+                    label: "start".into(),
+                    body: "#.\n.#".into()
+                },
+                Frame {
+                    label: "end: done".into(),
+                    body: "\nx".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_lines_before_first_frame() {
+        let frames = parse_frames("garbage\n=== frame 1: a\nbody");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].body, "body");
+    }
+
+    #[test]
+    fn formats_grids() {
+        assert_eq!(format_grid(vec![vec!['#', '.'], vec!['.', '#']]), "#.\n.#");
+    }
+}